@@ -0,0 +1,298 @@
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+
+use color_eyre::{eyre::eyre, Result, Section};
+use serde::{Deserialize, Serialize};
+
+use crate::{compat::SDK_FEATURE, json_file::JsonFile, outcome::TxOutcome, wallets};
+
+/// Default directory of the baselines, relative to the project root.
+pub const DEFAULT_BASELINE_DIR: &str = "gas-baselines";
+
+/// What [`Costs::finish`] does with the measured costs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BaselineMode {
+    /// Nothing, the costs are only measured.
+    #[default]
+    Off,
+    /// Writes them to the baseline, keeping the tolerances already there.
+    Record,
+    /// Fails the test if they moved beyond the tolerances of the baseline.
+    Compare,
+}
+
+impl FromStr for BaselineMode {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "off" => Ok(Self::Off),
+            "record" => Ok(Self::Record),
+            "compare" => Ok(Self::Compare),
+            other => Err(eyre!("invalid gas baseline mode: {other}")
+                .suggestion("use 'off', 'record' or 'compare'")),
+        }
+    }
+}
+
+impl std::fmt::Display for BaselineMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Record => write!(f, "record"),
+            Self::Compare => write!(f, "compare"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaselineConfig {
+    pub mode: BaselineMode,
+    /// The baseline file of the enabled SDK feature and the target chain, see [`baseline_path`].
+    pub path: PathBuf,
+}
+
+impl Default for BaselineConfig {
+    fn default() -> Self {
+        Self {
+            mode: BaselineMode::Off,
+            path: baseline_path(DEFAULT_BASELINE_DIR, "local"),
+        }
+    }
+}
+
+/// `<dir>/<sdk feature>-<chain>.json`. Costs differ between SDK versions and between chains, a
+/// file each keeps runs of one from overwriting the entries of another.
+pub fn baseline_path(dir: impl Into<PathBuf>, chain: impl std::fmt::Display) -> PathBuf {
+    dir.into().join(format!("{SDK_FEATURE}-{chain}.json"))
+}
+
+/// How far a cost may move, in percent of the baseline. Exact by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tolerance {
+    #[serde(default)]
+    pub gas: f64,
+    #[serde(default)]
+    pub fee: f64,
+}
+
+impl Tolerance {
+    fn is_exact(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub gas: u64,
+    pub fee: u64,
+    /// Only edited by hand, recording keeps it.
+    #[serde(default, skip_serializing_if = "Tolerance::is_exact")]
+    pub tolerance: Tolerance,
+}
+
+/// Gas and fee of every named transaction of the suite, keyed by `<test>/<transaction>`. Meant to
+/// be committed so that compiler and node upgrades that change costs show up as failures.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub entries: BTreeMap<String, BaselineEntry>,
+}
+
+impl JsonFile for Baseline {
+    const DESCRIPTION: &'static str = "gas baseline";
+    const INVALID_SUGGESTION: &'static str =
+        "fix the file or record it again with GAS_BASELINE=record";
+}
+
+impl Baseline {
+    /// Compares `measured` with the entries of the same name, in the order given.
+    pub fn compare(&self, measured: &[(String, Measurement)]) -> Comparison {
+        let rows = measured
+            .iter()
+            .map(|(name, measurement)| Row {
+                name: name.clone(),
+                baseline: self.entries.get(name).cloned(),
+                measured: *measurement,
+            })
+            .collect();
+
+        Comparison { rows }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub gas: u64,
+    pub fee: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub name: String,
+    /// `None` if the transaction isn't in the baseline yet.
+    pub baseline: Option<BaselineEntry>,
+    pub measured: Measurement,
+}
+
+impl Row {
+    pub fn within_tolerance(&self) -> bool {
+        let Some(baseline) = &self.baseline else {
+            return false;
+        };
+
+        within(baseline.gas, self.measured.gas, baseline.tolerance.gas)
+            && within(baseline.fee, self.measured.fee, baseline.tolerance.fee)
+    }
+}
+
+fn within(baseline: u64, measured: u64, tolerance: f64) -> bool {
+    baseline.abs_diff(measured) as f64 <= baseline as f64 * tolerance / 100.0
+}
+
+/// Change from `baseline` to `measured`, e.g. `1000 -> 1050 (+5.00%)`.
+fn change(baseline: u64, measured: u64) -> String {
+    if baseline == 0 {
+        return format!("{baseline} -> {measured}");
+    }
+
+    let percent = (measured as f64 - baseline as f64) / baseline as f64 * 100.0;
+    format!("{baseline} -> {measured} ({percent:+.2}%)")
+}
+
+/// The measured costs next to the baseline, printed as a table.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub rows: Vec<Row>,
+}
+
+impl Comparison {
+    pub fn passed(&self) -> bool {
+        self.rows.iter().all(Row::within_tolerance)
+    }
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cells = self
+            .rows
+            .iter()
+            .map(|row| {
+                let (gas, fee) = match &row.baseline {
+                    Some(baseline) => (
+                        change(baseline.gas, row.measured.gas),
+                        change(baseline.fee, row.measured.fee),
+                    ),
+                    None => (
+                        format!("new: {}", row.measured.gas),
+                        format!("new: {}", row.measured.fee),
+                    ),
+                };
+                let status = match (&row.baseline, row.within_tolerance()) {
+                    (None, _) => "MISSING",
+                    (Some(_), true) => "ok",
+                    (Some(_), false) => "MOVED",
+                };
+
+                [row.name.clone(), gas, fee, status.to_string()]
+            })
+            .collect::<Vec<_>>();
+
+        let header = ["transaction", "gas", "fee", ""].map(String::from);
+        let widths = [0, 1, 2].map(|column| {
+            std::iter::once(&header)
+                .chain(&cells)
+                .map(|row| row[column].len())
+                .max()
+                .unwrap_or_default()
+        });
+
+        for row in std::iter::once(&header).chain(&cells) {
+            let line = format!(
+                "  {:w0$}  {:w1$}  {:w2$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+            );
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Gas and fee of the named transactions of a test, recorded into or compared with the baseline
+/// by [`Costs::finish`] according to `GAS_BASELINE`.
+#[derive(Debug)]
+pub struct Costs {
+    config: BaselineConfig,
+    test: String,
+    measured: Vec<(String, Measurement)>,
+}
+
+impl Costs {
+    /// Entries are named after the running test.
+    pub fn new(config: &BaselineConfig) -> Result<Self> {
+        Ok(Self::named(config, &wallets::current_test_name()?))
+    }
+
+    /// Same as [`Costs::new`] but for callers not running inside a `#[test]`.
+    pub fn named(config: &BaselineConfig, test: &str) -> Self {
+        Self {
+            config: config.clone(),
+            test: test.to_string(),
+            measured: vec![],
+        }
+    }
+
+    /// Measures the transaction behind `outcome` as `<test>/<name>`.
+    pub fn record(&mut self, name: &str, outcome: &TxOutcome) {
+        self.measured.push((
+            format!("{}/{name}", self.test),
+            Measurement {
+                gas: outcome.total_gas,
+                fee: outcome.total_fee,
+            },
+        ));
+    }
+
+    pub fn measured(&self) -> &[(String, Measurement)] {
+        &self.measured
+    }
+
+    /// Records the costs into the baseline or compares them with it. Errors with the table of
+    /// every transaction of the test if any moved beyond its tolerance or isn't in the baseline.
+    pub fn finish(self) -> Result<()> {
+        let path = &self.config.path;
+
+        match self.config.mode {
+            BaselineMode::Off => Ok(()),
+            BaselineMode::Record => Baseline::update(path, |baseline| {
+                for (name, measurement) in self.measured {
+                    let entry = baseline.entries.entry(name).or_default();
+                    entry.gas = measurement.gas;
+                    entry.fee = measurement.fee;
+                }
+            }),
+            BaselineMode::Compare => {
+                let comparison = Baseline::load(path)?.compare(&self.measured);
+                if comparison.passed() {
+                    return Ok(());
+                }
+
+                Err(eyre!(
+                    "costs of {} moved from the baseline {}:\n{comparison}",
+                    self.test,
+                    path.display()
+                )
+                .suggestion(
+                    "if the change is expected, record it with GAS_BASELINE=record and commit \
+                     the baseline, or raise the entry's tolerance",
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Lists and prunes the deployment registry.
+//!
+//! ```text
+//! cargo run --bin deployments -- list [--registry <path>]
+//! cargo run --bin deployments -- prune [--registry <path>] [--older-than <days>] [--missing]
+//! ```
+fuel_e2e_tests::define_fuels!();
+
+use std::collections::HashMap;
+
+use color_eyre::{
+    eyre::{bail, eyre, Context},
+    Result, Section,
+};
+use fuel_e2e_tests::{
+    json_file::JsonFile,
+    registry::{self, Deployment, Registry, DEFAULT_REGISTRY_PATH},
+    retry,
+};
+use fuels::{
+    accounts::provider::Provider,
+    types::{bech32::Bech32ContractId, ContractId},
+};
+
+const USAGE: &str =
+    "usage: deployments <list|prune> [--registry <path>] [--older-than <days>] [--missing]";
+
+struct Args {
+    command: String,
+    registry: String,
+    older_than_days: Option<u64>,
+    missing: bool,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = std::env::args().skip(1);
+    let command = args
+        .next()
+        .ok_or_else(|| eyre!("no command given").suggestion(USAGE))?;
+
+    let mut parsed = Args {
+        command,
+        registry: std::env::var("DEPLOYMENT_REGISTRY")
+            .unwrap_or_else(|_| DEFAULT_REGISTRY_PATH.to_string()),
+        older_than_days: None,
+        missing: false,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| eyre!("{arg} expects a value").suggestion(USAGE))
+        };
+
+        match arg.as_str() {
+            "--registry" => parsed.registry = value()?,
+            "--older-than" => {
+                let days = value()?;
+                parsed.older_than_days = Some(
+                    days.parse()
+                        .wrap_err_with(|| format!("invalid number of days: {days}"))?,
+                );
+            }
+            "--missing" => parsed.missing = true,
+            other => bail!("unknown argument {other}\n{USAGE}"),
+        }
+    }
+
+    Ok(parsed)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    color_eyre::install()?;
+    let args = parse_args()?;
+
+    match args.command.as_str() {
+        "list" => list(&Registry::load(&args.registry)?),
+        "prune" => prune(&args).await,
+        other => Err(eyre!("unknown command {other}").suggestion(USAGE)),
+    }
+}
+
+fn list(registry: &Registry) -> Result<()> {
+    let now = registry::now();
+
+    for deployment in &registry.deployments {
+        let deployed = deployment
+            .deployed_at
+            .map(|at| format!("deployed {} ago", age(now, at)))
+            .unwrap_or_else(|| "found on chain".to_string());
+        let tx = deployment
+            .tx_id
+            .map(|tx_id| format!(" in tx {tx_id}"))
+            .unwrap_or_default();
+
+        println!(
+            "{} ({}, {} blobs)",
+            Bech32ContractId::from(deployment.contract_id),
+            deployment.kind,
+            deployment.blob_ids.len()
+        );
+        println!(
+            "  chain {} at {}, {}",
+            deployment.chain_id, deployment.node_url, deployment.contract_bin
+        );
+        println!(
+            "  {deployed}{tx}, last used {} ago, salt {}",
+            age(now, deployment.last_used_at),
+            deployment.salt
+        );
+        if let Some(seed) = &deployment.seed {
+            println!(
+                "  seed {seed}, instance {}",
+                deployment.instance.as_deref().unwrap_or("<none>")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn prune(args: &Args) -> Result<()> {
+    if args.older_than_days.is_none() && !args.missing {
+        bail!("nothing to prune\n{USAGE}");
+    }
+
+    // queried up front, `Registry::update` takes a sync closure
+    let missing = if args.missing {
+        missing(&Registry::load(&args.registry)?.deployments).await
+    } else {
+        vec![]
+    };
+
+    let cutoff = args
+        .older_than_days
+        .map(|days| registry::now().saturating_sub(days * 24 * 60 * 60));
+
+    let pruned = Registry::update(&args.registry, |registry| {
+        registry.prune(|deployment| {
+            cutoff.is_some_and(|cutoff| deployment.last_used_at < cutoff)
+                || missing.contains(&(deployment.contract_id, deployment.node_url.clone()))
+        })
+    })?;
+
+    for deployment in &pruned {
+        println!(
+            "pruned {} on chain {} at {}",
+            Bech32ContractId::from(deployment.contract_id),
+            deployment.chain_id,
+            deployment.node_url
+        );
+    }
+    println!("{} deployment(s) pruned", pruned.len());
+
+    Ok(())
+}
+
+/// The deployments the node they were deployed to reports as absent. Deployments whose node
+/// can't be reached or queried are kept and reported, so that an outage doesn't prune live
+/// contracts.
+async fn missing(deployments: &[Deployment]) -> Vec<(ContractId, String)> {
+    let policy = retry::policy();
+    let mut providers: HashMap<&str, Option<Provider>> = HashMap::new();
+    let mut missing = vec![];
+
+    for deployment in deployments {
+        let url = deployment.node_url.as_str();
+        if !providers.contains_key(url) {
+            let provider = policy
+                .query(&format!("connecting to {url}"), || async {
+                    Ok(Provider::connect(url).await?)
+                })
+                .await;
+            if let Err(err) = &provider {
+                eprintln!("keeping the deployments at {url}, failed to connect: {err:#}");
+            }
+            providers.insert(url, provider.ok());
+        }
+        let Some(provider) = &providers[url] else {
+            continue;
+        };
+
+        let contract_id = Bech32ContractId::from(deployment.contract_id);
+        let exists = policy
+            .query("contract query", || async {
+                Ok(provider.contract_exists(&contract_id).await?)
+            })
+            .await;
+        match exists {
+            Ok(true) => {}
+            Ok(false) => missing.push((deployment.contract_id, deployment.node_url.clone())),
+            Err(err) => {
+                eprintln!("keeping {contract_id} at {url}, failed to query it: {err:#}")
+            }
+        }
+    }
+
+    missing
+}
+
+fn age(now: u64, at: u64) -> String {
+    let secs = now.saturating_sub(at);
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86400 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
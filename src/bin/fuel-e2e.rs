@@ -0,0 +1,217 @@
+//! Runs the suites of the harness against a chain, for running the checks without cargo.
+//!
+//! ```text
+//! cargo run --bin fuel-e2e -- list [--scenarios <dir>]
+//! cargo run --bin fuel-e2e -- run [<suite>...] [--chain <chain>] [--node-url <url>]
+//!     [--profile <name>] [--force-deploy] [--deploy-seed <seed>]
+//!     [--deploy-strategy <strategy>] [--blob-strategy <strategy>]
+//!     [--existing-contracts <name>=<id>,..] [--deployment-registry <path>]
+//!     [--report-dir <dir>] [--scenarios <dir>]
+//! ```
+//!
+//! Every suite runs if none is given. The flags take precedence over the env variables of the
+//! same name, anything not given is resolved from the env, `.env` and `e2e.toml` as under
+//! `cargo test`. The built-in suites are the same functions the `cargo test`s of the same name
+//! run, see `suites::run_test`.
+fuel_e2e_tests::define_fuels!();
+
+use color_eyre::{
+    eyre::{bail, eyre},
+    Result, Section,
+};
+use fuel_e2e_tests::{
+    cleanup::{self, ReclaimScope},
+    config::{Config, Flags},
+    registry,
+    report::RunReport,
+    scenario::DEFAULT_SCENARIO_DIR,
+    setup,
+    suites::{self, Suite, SuiteRun},
+};
+
+const USAGE: &str = "usage: fuel-e2e <list|run> [<suite>...] [--chain <chain>] \
+                     [--node-url <url>] [--profile <name>] [--force-deploy] \
+                     [--deploy-seed <seed>] [--deploy-strategy <strategy>] \
+                     [--blob-strategy <strategy>] \
+                     [--existing-contracts <name>=<id>,..] [--deployment-registry <path>] \
+                     [--report-dir <dir>] [--scenarios <dir>]";
+
+/// Flags standing in for the env variable the corresponding setting is resolved from.
+const ENV_FLAGS: [(&str, &str); 9] = [
+    ("--chain", "TARGET_CHAIN"),
+    ("--node-url", "NODE_URL"),
+    ("--profile", "E2E_PROFILE"),
+    ("--deploy-seed", "DEPLOY_SEED"),
+    ("--deploy-strategy", "DEPLOY_STRATEGY"),
+    ("--blob-strategy", "BLOB_STRATEGY"),
+    ("--existing-contracts", "EXISTING_CONTRACTS"),
+    ("--deployment-registry", "DEPLOYMENT_REGISTRY"),
+    ("--report-dir", "REPORT_DIR"),
+];
+
+struct Args {
+    command: String,
+    suites: Vec<String>,
+    scenarios: String,
+    flags: Flags,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = std::env::args().skip(1);
+    let command = args
+        .next()
+        .ok_or_else(|| eyre!("no command given").suggestion(USAGE))?;
+
+    let mut parsed = Args {
+        command,
+        suites: vec![],
+        scenarios: DEFAULT_SCENARIO_DIR.to_string(),
+        flags: Flags::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| eyre!("{arg} expects a value").suggestion(USAGE))
+        };
+
+        if let Some((_, env)) = ENV_FLAGS.iter().find(|(flag, _)| *flag == arg) {
+            parsed.flags.insert(env.to_string(), value()?);
+            continue;
+        }
+
+        match arg.as_str() {
+            "--force-deploy" => {
+                parsed
+                    .flags
+                    .insert("FORCE_DEPLOY".to_string(), "true".to_string());
+            }
+            "--scenarios" => parsed.scenarios = value()?,
+            flag if flag.starts_with("--") => bail!("unknown argument {flag}\n{USAGE}"),
+            _ => parsed.suites.push(arg),
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let args = parse_args()?;
+    let suites = suites::all(&args.scenarios)?;
+
+    match args.command.as_str() {
+        "list" => {
+            list(&suites);
+            Ok(())
+        }
+        "run" => {
+            let selected = select(&suites, &args.suites)?;
+            // before the runtime starts its threads, loading `.env` sets env variables
+            let config = Config::load_with_flags(&args.flags)?;
+
+            tokio::runtime::Runtime::new()?.block_on(run(config, selected))
+        }
+        other => Err(eyre!("unknown command {other}").suggestion(USAGE)),
+    }
+}
+
+fn list(suites: &[Suite]) {
+    let width = suites.iter().map(|suite| suite.name.len()).max();
+
+    for suite in suites {
+        println!(
+            "{:width$}  {}",
+            suite.name,
+            suite.description,
+            width = width.unwrap_or_default()
+        );
+    }
+}
+
+async fn run(config: Config, selected: Vec<&Suite>) -> Result<()> {
+    let setup = setup::init_with(config).await?;
+    let started_at = registry::now();
+
+    let mut runs = vec![];
+    for suite in selected {
+        eprintln!("running {}", suite.name);
+        let run = suite.run(&setup).await;
+        if let Err(err) = &run.result {
+            eprintln!("{} failed:\n{err:?}", run.name);
+        }
+        runs.push(run);
+    }
+
+    // suites that failed midway leave their holdings recorded, nothing else will run after them
+    match cleanup::reclaim(&setup, ReclaimScope::ThisProcess).await {
+        Ok(report) if report.reclaimed.is_empty() && report.kept.is_empty() => {}
+        Ok(report) => eprintln!("{report}"),
+        Err(err) => eprintln!("failed to reclaim the holdings of this run:\n{err:?}"),
+    }
+
+    if let Some(dir) = &setup.report_dir {
+        let (json, xml) = RunReport::new(&setup, started_at, &runs)
+            .await?
+            .write(dir)?;
+        eprintln!(
+            "reports written to {} and {}",
+            json.display(),
+            xml.display()
+        );
+    }
+
+    let failed = summarize(&runs);
+    if failed > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// The suites named in `names`, in the order given, or all of them if none is.
+fn select<'a>(suites: &'a [Suite], names: &[String]) -> Result<Vec<&'a Suite>> {
+    if names.is_empty() {
+        return Ok(suites.iter().collect());
+    }
+
+    names
+        .iter()
+        .map(|name| {
+            suites
+                .iter()
+                .find(|suite| suite.name == *name)
+                .ok_or_else(|| {
+                    eyre!("unknown suite {name}").suggestion("list them with `fuel-e2e list`")
+                })
+        })
+        .collect()
+}
+
+/// Prints a line per suite and returns how many failed.
+fn summarize(runs: &[SuiteRun]) -> usize {
+    let width = runs.iter().map(|run| run.name.len()).max();
+
+    println!("summary:");
+    for run in runs {
+        let (status, detail) = match &run.result {
+            Ok(()) => ("ok", format!("{} transactions", run.transactions.len())),
+            Err(err) => ("FAILED", format!("{err:#}")),
+        };
+        println!(
+            "  {:width$}  {status:6}  {:>7.2}s  {detail}",
+            run.name,
+            run.duration.as_secs_f64(),
+            width = width.unwrap_or_default()
+        );
+    }
+
+    let failed = runs.iter().filter(|run| !run.passed()).count();
+    println!(
+        "{} suites: {} passed, {failed} failed",
+        runs.len(),
+        runs.len() - failed
+    );
+
+    failed
+}
@@ -0,0 +1,310 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    sync::{LazyLock, Mutex},
+    time::Duration,
+};
+
+use color_eyre::{eyre::eyre, Result, Section};
+use fuels::{
+    accounts::{predicate::Predicate, provider::Provider, ViewOnlyAccount},
+    types::ContractId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    helpers, hex_bytes,
+    json_file::JsonFile,
+    liquidity_pool,
+    outcome::TxOutcome,
+    registry,
+    setup::{Setup, Wallet},
+    wallets,
+};
+
+/// Default location of the cleanup registry, relative to the project root.
+pub const DEFAULT_CLEANUP_PATH: &str = "cleanup.json";
+
+/// Entries of other processes younger than this may belong to a run still in progress, e.g. the
+/// other feature's tests running in parallel on CI, and are left alone.
+pub const ABANDONED_AFTER: Duration = Duration::from_secs(15 * 60);
+
+/// Set by `Config::setup`, `None` disables the registry.
+static PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Those of the fixtures of this crate, so that their deposits are reclaimed by the first pass of
+/// a process rather than only once a test has deployed the fixture again.
+static RECLAIMERS: LazyLock<Mutex<HashMap<String, Reclaimer>>> = LazyLock::new(|| {
+    let builtin: [(&str, Reclaimer); 1] = [(liquidity_pool::RECLAIMER, liquidity_pool::reclaim)];
+
+    Mutex::new(
+        builtin
+            .into_iter()
+            .map(|(name, reclaimer)| (name.to_string(), reclaimer))
+            .collect(),
+    )
+});
+
+/// Withdraws whatever the master wallet deposited into the contract.
+pub type Reclaimer =
+    fn(Wallet, ContractId) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'static>>;
+
+/// Something that received funds from the master wallet and can be emptied again without the
+/// test that funded it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Holding {
+    /// A fresh wallet, its key is derived again from the master key and the name.
+    Wallet { name: String },
+    /// Spent with the same code and data that the fixture used.
+    Predicate {
+        #[serde(with = "hex_bytes")]
+        code: Vec<u8>,
+        #[serde(with = "hex_bytes")]
+        data: Vec<u8>,
+    },
+    /// A deposit of the master wallet, withdrawn by the [`Reclaimer`] registered under
+    /// `reclaimer`.
+    Deposit {
+        contract_id: ContractId,
+        reclaimer: String,
+    },
+}
+
+impl std::fmt::Display for Holding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wallet { name } => write!(f, "fresh wallet '{name}'"),
+            Self::Predicate { code, .. } => {
+                write!(
+                    f,
+                    "predicate {}",
+                    Predicate::from_code(code.clone()).address()
+                )
+            }
+            Self::Deposit {
+                contract_id,
+                reclaimer,
+            } => write!(f, "deposit into {contract_id:#x} ({reclaimer})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub chain_id: u64,
+    pub node_url: String,
+    #[serde(flatten)]
+    pub holding: Holding,
+    /// Process id of the run that recorded it.
+    pub recorded_by: u32,
+    /// Unix timestamp, refreshed every time the holding is funded again.
+    pub recorded_at: u64,
+}
+
+/// Every address the fixtures sent funds to, persisted as JSON so that a later run can sweep
+/// what a killed one left behind.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CleanupRegistry {
+    pub entries: Vec<Entry>,
+}
+
+impl JsonFile for CleanupRegistry {
+    const DESCRIPTION: &'static str = "cleanup registry";
+    const INVALID_SUGGESTION: &'static str =
+        "fix or delete the file, funds it points to won't be reclaimed";
+}
+
+impl CleanupRegistry {
+    /// Adds `entry`, or refreshes it if the holding is already registered for the chain.
+    pub fn record(&mut self, entry: Entry) {
+        match self.position(entry.chain_id, &entry.node_url, &entry.holding) {
+            Some(index) => self.entries[index] = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// Removes the entry of `holding`, returns whether there was one.
+    pub fn forget(&mut self, chain_id: u64, node_url: &str, holding: &Holding) -> bool {
+        self.position(chain_id, node_url, holding)
+            .map(|index| self.entries.remove(index))
+            .is_some()
+    }
+
+    fn position(&self, chain_id: u64, node_url: &str, holding: &Holding) -> Option<usize> {
+        self.entries.iter().position(|entry| {
+            entry.chain_id == chain_id && entry.node_url == node_url && entry.holding == *holding
+        })
+    }
+}
+
+/// Where fixtures record their holdings from now on, `None` to stop recording.
+pub fn set_path(path: Option<PathBuf>) {
+    *PATH.lock().expect("not poisoned") = path;
+}
+
+pub fn path() -> Option<PathBuf> {
+    PATH.lock().expect("not poisoned").clone()
+}
+
+/// Makes deposits recorded under `name` reclaimable by this process. Only needed for fixtures
+/// outside this crate, which have to register before the first [`reclaim`] pass, i.e. before
+/// `setup::init`, for their deposits of killed runs to be picked up.
+pub fn register_reclaimer(name: &str, reclaimer: Reclaimer) {
+    RECLAIMERS
+        .lock()
+        .expect("not poisoned")
+        .insert(name.to_string(), reclaimer);
+}
+
+/// Records that `holding` received funds on the chain of `provider`. A no-op if the registry is
+/// disabled.
+pub async fn record(provider: &Provider, holding: Holding) -> Result<()> {
+    let Some(path) = path() else {
+        return Ok(());
+    };
+
+    let entry = Entry {
+        chain_id: provider.consensus_parameters().await?.chain_id().into(),
+        node_url: provider.url().to_string(),
+        holding,
+        recorded_by: std::process::id(),
+        recorded_at: registry::now(),
+    };
+
+    CleanupRegistry::update(path, |registry| registry.record(entry))
+}
+
+/// Records that `holding` was emptied by the test that funded it.
+pub async fn forget(provider: &Provider, holding: &Holding) -> Result<()> {
+    let Some(path) = path() else {
+        return Ok(());
+    };
+
+    let chain_id = provider.consensus_parameters().await?.chain_id().into();
+    CleanupRegistry::update(path, |registry| {
+        registry.forget(chain_id, provider.url(), holding);
+    })
+}
+
+/// Which entries a [`reclaim`] pass goes through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReclaimScope {
+    /// Those of other processes not refreshed for [`ABANDONED_AFTER`]. Run by `setup::init`.
+    Abandoned,
+    /// Those of this process. Run by `fuel-e2e run` once its suites are done, `cargo test` has no
+    /// hook after the last test so what its tests leave behind waits for the next `Abandoned` pass.
+    ThisProcess,
+    /// All of them, including those of this process. Only safe once no test is running anymore.
+    All,
+}
+
+#[derive(Debug, Default)]
+pub struct ReclaimReport {
+    /// What was swept back to the master wallet, with the outcome of the transfer if anything
+    /// was left to transfer. Deposits have no outcome.
+    pub reclaimed: Vec<(Holding, Option<TxOutcome>)>,
+    /// What is still recorded, and why.
+    pub kept: Vec<(Holding, String)>,
+}
+
+impl std::fmt::Display for ReclaimReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "reclaimed {} holdings, kept {}:",
+            self.reclaimed.len(),
+            self.kept.len()
+        )?;
+        for (holding, outcome) in &self.reclaimed {
+            match outcome {
+                Some(outcome) => writeln!(f, "  {holding}: swept in {}", outcome.tx_id)?,
+                None => writeln!(f, "  {holding}: nothing left")?,
+            }
+        }
+        for (holding, reason) in &self.kept {
+            writeln!(f, "  {holding}: kept, {reason}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Sweeps the holdings recorded for the chain of `setup` back to its wallet and forgets those
+/// that were emptied. Failures are reported rather than returned, the entry stays for the next
+/// pass.
+pub async fn reclaim(setup: &Setup, scope: ReclaimScope) -> Result<ReclaimReport> {
+    let mut report = ReclaimReport::default();
+    let Some(path) = path() else {
+        return Ok(report);
+    };
+
+    let provider = setup.wallet.try_provider()?;
+    let chain_id: u64 = provider.consensus_parameters().await?.chain_id().into();
+    let node_url = provider.url().to_string();
+    let now = registry::now();
+
+    let entries = CleanupRegistry::load(&path)?
+        .entries
+        .into_iter()
+        .filter(|entry| entry.chain_id == chain_id && entry.node_url == node_url)
+        .filter(|entry| match scope {
+            ReclaimScope::Abandoned => {
+                entry.recorded_by != std::process::id()
+                    && now.saturating_sub(entry.recorded_at) >= ABANDONED_AFTER.as_secs()
+            }
+            ReclaimScope::ThisProcess => entry.recorded_by == std::process::id(),
+            ReclaimScope::All => true,
+        });
+
+    for entry in entries {
+        match sweep(setup, provider, &entry.holding).await {
+            Ok(outcome) => {
+                CleanupRegistry::update(&path, |registry| {
+                    registry.forget(chain_id, &node_url, &entry.holding)
+                })?;
+                report.reclaimed.push((entry.holding, outcome));
+            }
+            Err(err) => report.kept.push((entry.holding, format!("{err:#}"))),
+        }
+    }
+
+    Ok(report)
+}
+
+async fn sweep(setup: &Setup, provider: &Provider, holding: &Holding) -> Result<Option<TxOutcome>> {
+    let master = setup.wallet.address();
+
+    match holding {
+        Holding::Wallet { name } => {
+            let wallet = wallets::derive_wallet(&setup.master_key.0, name, provider.clone());
+            helpers::transfer_all(&wallet, master).await
+        }
+        Holding::Predicate { code, data } => {
+            let predicate = Predicate::from_code(code.clone())
+                .with_data(data.clone())
+                .with_provider(provider.clone());
+            helpers::transfer_all(&predicate, master).await
+        }
+        Holding::Deposit {
+            contract_id,
+            reclaimer,
+        } => {
+            let reclaim = RECLAIMERS
+                .lock()
+                .expect("not poisoned")
+                .get(reclaimer)
+                .copied()
+                .ok_or_else(|| {
+                    eyre!("no reclaimer registered under '{reclaimer}'").suggestion(
+                        "register it with `cleanup::register_reclaimer` before `setup::init`",
+                    )
+                })?;
+            reclaim(setup.wallet.clone(), *contract_id).await?;
+
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! One API over what differs between fuels 0.70 and 0.71, so that the tests compile unchanged
+//! against either version. Everything feature specific lives here.
+
+use color_eyre::Result;
+use fuels::{
+    accounts::provider::Provider,
+    types::{bech32::Bech32Address, transaction::Transaction, AssetId, Bytes32},
+};
+
+use crate::outcome::TxOutcome;
+
+#[cfg(feature = "fuels_lts_70")]
+mod fuels_lts_70_compat {
+    use color_eyre::{eyre::eyre, Result};
+    use fuels::{
+        accounts::{provider::Provider, Account},
+        crypto::SecretKey,
+        programs::responses::CallResponse,
+        types::{bech32::Bech32Address, transaction::TxPolicies, AssetId},
+    };
+
+    use super::{CallResponseCompat, TransferCompat};
+    use crate::outcome::{ProviderExt, TxOutcome};
+
+    pub type SdkWallet = fuels::accounts::wallet::WalletUnlocked;
+
+    /// The feature the harness was built with, as reported in the run reports.
+    pub const SDK_FEATURE: &str = "fuels_lts_70";
+
+    /// Not wrapped in a coin reserving wallet, see `setup::wallet_from_key` for that.
+    pub fn wallet_from_key(key: SecretKey, provider: Provider) -> SdkWallet {
+        SdkWallet::new_from_private_key(key, Some(provider))
+    }
+
+    impl<D> CallResponseCompat for CallResponse<D> {
+        async fn total_fee(&self, provider: &Provider) -> Result<u64> {
+            let tx_id = self
+                .tx_id
+                .ok_or_else(|| eyre!("the call response has no tx id"))?;
+
+            provider.tx_total_fee(&tx_id).await
+        }
+    }
+
+    impl<T: Account> TransferCompat for T {
+        async fn transfer_with_outcome(
+            &self,
+            to: &Bech32Address,
+            amount: u64,
+            asset_id: AssetId,
+        ) -> Result<TxOutcome> {
+            let (tx_id, _) = self
+                .transfer(to, amount, asset_id, TxPolicies::default())
+                .await?;
+
+            TxOutcome::fetch(self.try_provider()?, &tx_id).await
+        }
+    }
+}
+#[cfg(feature = "fuels_lts_70")]
+pub use fuels_lts_70_compat::*;
+
+#[cfg(feature = "fuels_71")]
+mod fuels_71_compat {
+    use color_eyre::Result;
+    use fuels::{
+        accounts::{provider::Provider, signers::private_key::PrivateKeySigner, Account},
+        crypto::SecretKey,
+        programs::responses::CallResponse,
+        types::{bech32::Bech32Address, transaction::TxPolicies, AssetId},
+    };
+
+    use super::{CallResponseCompat, TransferCompat};
+    use crate::outcome::TxOutcome;
+
+    pub type SdkWallet = fuels::accounts::wallet::Wallet;
+
+    /// The feature the harness was built with, as reported in the run reports.
+    pub const SDK_FEATURE: &str = "fuels_71";
+
+    /// Not wrapped in a coin reserving wallet, see `setup::wallet_from_key` for that.
+    pub fn wallet_from_key(key: SecretKey, provider: Provider) -> SdkWallet {
+        SdkWallet::new(PrivateKeySigner::new(key), provider)
+    }
+
+    impl<D> CallResponseCompat for CallResponse<D> {
+        async fn total_fee(&self, _provider: &Provider) -> Result<u64> {
+            Ok(self.tx_status.total_fee)
+        }
+    }
+
+    impl<T: Account> TransferCompat for T {
+        async fn transfer_with_outcome(
+            &self,
+            to: &Bech32Address,
+            amount: u64,
+            asset_id: AssetId,
+        ) -> Result<TxOutcome> {
+            let response = self
+                .transfer(to, amount, asset_id, TxPolicies::default())
+                .await?;
+
+            TxOutcome::fetch(self.try_provider()?, &response.tx_id).await
+        }
+    }
+}
+#[cfg(feature = "fuels_71")]
+pub use fuels_71_compat::*;
+
+#[allow(async_fn_in_trait)]
+pub trait CallResponseCompat {
+    /// Queried from the node under 0.70, part of the response under 0.71.
+    async fn total_fee(&self, provider: &Provider) -> Result<u64>;
+}
+
+#[allow(async_fn_in_trait)]
+pub trait TransactionCompat {
+    /// Id of the built transaction on the chain `provider` is connected to.
+    async fn tx_id(&self, provider: &Provider) -> Result<Bytes32>;
+}
+
+impl<T: Transaction> TransactionCompat for T {
+    async fn tx_id(&self, provider: &Provider) -> Result<Bytes32> {
+        let chain_id = provider.consensus_parameters().await?.chain_id();
+
+        Ok(self.id(chain_id))
+    }
+}
+
+#[allow(async_fn_in_trait)]
+pub trait TransferCompat {
+    /// `Account::transfer` with the default policies. It returns the tx id and receipts under 0.70
+    /// and a `TxResponse` under 0.71, this returns the outcome under both.
+    async fn transfer_with_outcome(
+        &self,
+        to: &Bech32Address,
+        amount: u64,
+        asset_id: AssetId,
+    ) -> Result<TxOutcome>;
+}
@@ -0,0 +1,686 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result, Section,
+};
+use serde::Deserialize;
+
+use crate::{
+    baseline::{self, BaselineConfig, BaselineMode, DEFAULT_BASELINE_DIR},
+    cleanup::{self, DEFAULT_CLEANUP_PATH},
+    keys::KeyEnvs,
+    local_node::LocalNodeConfig,
+    registry::DEFAULT_REGISTRY_PATH,
+    retry::{self, ErrorClasses, RetryPolicy, DEFAULT_ATTEMPTS, DEFAULT_BACKOFF},
+    setup::{
+        load_dotenv, parse_boolean_env, read_env, BlobStrategy, Chain, DeployConfig,
+        DeployStrategy, ExistingContracts, MasterKey, Setup, DEFAULT_DEPLOY_SEED,
+    },
+};
+
+/// Default location of the config file, relative to the project root.
+pub const DEFAULT_CONFIG_PATH: &str = "e2e.toml";
+
+/// Contents of `e2e.toml`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    #[serde(default)]
+    pub profile: BTreeMap<String, Profile>,
+}
+
+/// A named set of settings. Every field is optional so that a profile only needs to mention what
+/// differs from the defaults. Env variables take precedence over anything set here.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Profile {
+    pub target_chain: Option<TargetChain>,
+    /// Only used when `target-chain = "custom"`.
+    pub node_url: Option<String>,
+    pub force_deploy: Option<bool>,
+    pub deploy_seed: Option<String>,
+    pub deploy_strategy: Option<DeployStrategy>,
+    /// Only used by the loader strategies.
+    pub blob_strategy: Option<BlobStrategy>,
+    /// Contract ids by binary name, e.g. `existing-contracts = { counter = "0x12.." }`.
+    pub existing_contracts: Option<ExistingContracts>,
+    /// Path of the deployment registry, an empty path disables it.
+    pub deployment_registry: Option<String>,
+    /// Path of the cleanup registry, an empty path disables it.
+    pub cleanup_registry: Option<String>,
+    pub gas_baseline: Option<BaselineMode>,
+    pub gas_baseline_dir: Option<String>,
+    /// Directory the reports of `fuel-e2e run` and of the built-in suites under `cargo test` are
+    /// written to, an empty path disables them.
+    pub report_dir: Option<String>,
+    pub retry_attempts: Option<u32>,
+    pub retry_backoff_ms: Option<u64>,
+    pub retry_on: Option<ErrorClasses>,
+    /// Only used when `target-chain = "local"`.
+    pub local_node: Option<LocalNodeConfig>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetChain {
+    Local,
+    Devnet,
+    Testnet,
+    Custom,
+}
+
+impl FromStr for TargetChain {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let chain = match s {
+            "local" => Self::Local,
+            "devnet" => Self::Devnet,
+            "testnet" => Self::Testnet,
+            "custom" => Self::Custom,
+            other => {
+                return Err(eyre!("invalid target chain value: {other}")
+                    .suggestion("use 'local', 'devnet', 'testnet' or 'custom'"))
+            }
+        };
+
+        Ok(chain)
+    }
+}
+
+impl std::fmt::Display for TargetChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Local => write!(f, "local"),
+            Self::Devnet => write!(f, "devnet"),
+            Self::Testnet => write!(f, "testnet"),
+            Self::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+/// Settings given on the command line of `fuel-e2e`, by the name of the env variable they stand
+/// in for, e.g. `TARGET_CHAIN` for `--chain`. They take precedence over the env.
+pub type Flags = BTreeMap<String, String>;
+
+/// The configuration after merging the selected profile with the env overrides.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Name of the profile the settings were taken from, if any.
+    pub profile: Option<String>,
+    pub target_chain: TargetChain,
+    pub node_url: Option<String>,
+    pub deploy_config: DeployConfig,
+    /// Where fixtures record the addresses they fund, see [`cleanup`].
+    pub cleanup_registry: Option<PathBuf>,
+    pub baseline: BaselineConfig,
+    /// Where the reports of the suites are written, see [`crate::report`].
+    pub report_dir: Option<PathBuf>,
+    /// How transient failures of the node are retried, see [`crate::retry`].
+    pub retry: RetryPolicy,
+    pub local_node: LocalNodeConfig,
+    settings: Vec<Setting>,
+}
+
+/// Where the value of a setting came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// A flag of `fuel-e2e`, see [`Flags`].
+    Flag,
+    Env,
+    DotEnv,
+    Profile(String),
+    Default,
+    /// Drawn at random because `FORCE_DEPLOY` is set.
+    ForceDeploy,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Flag => write!(f, "flag"),
+            Source::Env => write!(f, "env"),
+            Source::DotEnv => write!(f, ".env"),
+            Source::Profile(name) => write!(f, "profile '{name}'"),
+            Source::Default => write!(f, "default"),
+            Source::ForceDeploy => write!(f, "FORCE_DEPLOY"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Setting {
+    pub name: String,
+    /// `None` if the setting has no value. Private keys are always redacted.
+    pub value: Option<String>,
+    pub source: Source,
+}
+
+/// Every resolved setting, its source and the chain it targets. Printed by `setup::init`.
+#[derive(Debug, Clone)]
+pub struct ConfigReport {
+    pub chain: String,
+    pub settings: Vec<Setting>,
+}
+
+impl std::fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "e2e configuration targeting {}:", self.chain)?;
+
+        let name_width = self.settings.iter().map(|s| s.name.len()).max();
+        let value_width = self
+            .settings
+            .iter()
+            .map(|s| s.value.as_deref().unwrap_or("<unset>").len())
+            .max();
+
+        for setting in &self.settings {
+            let value = setting.value.as_deref().unwrap_or("<unset>");
+            writeln!(
+                f,
+                "  {:name_width$}  {:value_width$}  ({})",
+                setting.name,
+                value,
+                setting.source,
+                name_width = name_width.unwrap_or_default(),
+                value_width = value_width.unwrap_or_default(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ConfigFile {
+    pub fn parse(contents: &str) -> Result<Self> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    /// Reads the config file at `path`. A missing file is treated as an empty one.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+
+        Self::parse(&contents)
+            .wrap_err_with(|| format!("invalid config file {}", path.display()))
+            .suggestion("check the key names against the `Profile` fields, e.g. `deploy-strategy`")
+    }
+
+    pub fn profile(&self, name: &str) -> Result<&Profile> {
+        self.profile.get(name).ok_or_else(|| {
+            let available = self.profile.keys().cloned().collect::<Vec<_>>().join(", ");
+            eyre!("unknown profile: {name}")
+                .suggestion(format!("available profiles are: [{available}]"))
+        })
+    }
+}
+
+impl Config {
+    /// Loads `E2E_CONFIG` (defaults to [`DEFAULT_CONFIG_PATH`]), selects the profile named by
+    /// `E2E_PROFILE` (if any) and applies the env overrides on top of it.
+    pub fn load() -> Result<Self> {
+        Self::load_with_flags(&Flags::new())
+    }
+
+    /// Same as [`Config::load`], with `flags` taking precedence over the env.
+    pub fn load_with_flags(flags: &Flags) -> Result<Self> {
+        load_dotenv();
+
+        let path = read_flag_or_env(flags, "E2E_CONFIG")
+            .map_or_else(|| DEFAULT_CONFIG_PATH.to_string(), |(path, _)| path);
+        let file = ConfigFile::load(&path)?;
+
+        let (name, profile) = match read_flag_or_env(flags, "E2E_PROFILE").map(|(name, _)| name) {
+            Some(name) => {
+                let profile = file
+                    .profile(&name)
+                    .wrap_err_with(|| format!("failed to select profile from {path}"))?
+                    .clone();
+                (Some(name), profile)
+            }
+            None => (None, Profile::default()),
+        };
+
+        let mut config = Self::resolve_with_flags(name, profile, flags)?;
+        config.settings.splice(
+            0..0,
+            [
+                env_setting(flags, "E2E_CONFIG", DEFAULT_CONFIG_PATH),
+                env_setting(flags, "E2E_PROFILE", "<none>"),
+            ],
+        );
+
+        Ok(config)
+    }
+
+    /// Merges `profile` with the env overrides.
+    pub fn resolve(name: Option<String>, profile: Profile) -> Result<Self> {
+        Self::resolve_with_flags(name, profile, &Flags::new())
+    }
+
+    /// Same as [`Config::resolve`], with `flags` taking precedence over the env.
+    pub fn resolve_with_flags(
+        name: Option<String>,
+        profile: Profile,
+        flags: &Flags,
+    ) -> Result<Self> {
+        let mut resolver = Resolver {
+            profile: name.clone(),
+            flags,
+            settings: vec![],
+        };
+
+        let target_chain = resolver
+            .resolve("TARGET_CHAIN", str::parse, profile.target_chain, None)?
+            .ok_or_else(|| {
+                eyre!("no target chain given")
+                    .suggestion("set TARGET_CHAIN or `target-chain` in the selected profile")
+            })?;
+
+        let node_url = resolver.resolve(
+            "NODE_URL",
+            |value| Ok(value.to_string()),
+            profile.node_url,
+            None,
+        )?;
+
+        let force_deploy = resolver.resolve_bool("FORCE_DEPLOY", profile.force_deploy)?;
+
+        let deploy_seed = resolver
+            .resolve(
+                "DEPLOY_SEED",
+                |value| Ok(value.to_string()),
+                profile.deploy_seed,
+                Some(DEFAULT_DEPLOY_SEED.to_string()),
+            )?
+            .unwrap_or_default();
+        // a seed no earlier run used, whatever DEPLOY_SEED says. Reported like any other setting,
+        // so that a forced run can be reproduced.
+        let deploy_seed = if force_deploy {
+            let seed = format!("{:016x}", rand::random::<u64>());
+            resolver.replace("DEPLOY_SEED", &seed, Source::ForceDeploy);
+            seed
+        } else {
+            deploy_seed
+        };
+        let deploy_strategy = resolver
+            .resolve(
+                "DEPLOY_STRATEGY",
+                str::parse,
+                profile.deploy_strategy,
+                Some(DeployStrategy::default()),
+            )?
+            .unwrap_or_default();
+
+        let blob_strategy = resolver
+            .resolve(
+                "BLOB_STRATEGY",
+                str::parse,
+                profile.blob_strategy,
+                Some(BlobStrategy::default()),
+            )?
+            .unwrap_or_default();
+
+        let existing_contracts = resolver
+            .resolve(
+                "EXISTING_CONTRACTS",
+                str::parse,
+                profile.existing_contracts,
+                Some(ExistingContracts::default()),
+            )?
+            .unwrap_or_default();
+
+        // the local node starts from scratch every run, nothing to remember
+        let default_registry = match target_chain {
+            TargetChain::Local => None,
+            _ => Some(DEFAULT_REGISTRY_PATH.to_string()),
+        };
+        let registry = resolver
+            .resolve(
+                "DEPLOYMENT_REGISTRY",
+                |value| Ok(value.to_string()),
+                profile.deployment_registry,
+                default_registry,
+            )?
+            .filter(|path| !path.is_empty());
+
+        let default_cleanup_registry = match target_chain {
+            TargetChain::Local => None,
+            _ => Some(DEFAULT_CLEANUP_PATH.to_string()),
+        };
+        let cleanup_registry = resolver
+            .resolve(
+                "CLEANUP_REGISTRY",
+                |value| Ok(value.to_string()),
+                profile.cleanup_registry,
+                default_cleanup_registry,
+            )?
+            .filter(|path| !path.is_empty());
+
+        let baseline_mode = resolver
+            .resolve(
+                "GAS_BASELINE",
+                str::parse,
+                profile.gas_baseline,
+                Some(BaselineMode::default()),
+            )?
+            .unwrap_or_default();
+        let baseline_dir = resolver
+            .resolve(
+                "GAS_BASELINE_DIR",
+                |value| Ok(value.to_string()),
+                profile.gas_baseline_dir,
+                Some(DEFAULT_BASELINE_DIR.to_string()),
+            )?
+            .unwrap_or_default();
+
+        let report_dir = resolver
+            .resolve(
+                "REPORT_DIR",
+                |value| Ok(value.to_string()),
+                profile.report_dir,
+                None,
+            )?
+            .filter(|path| !path.is_empty());
+
+        let retry_attempts = resolver
+            .resolve(
+                "RETRY_ATTEMPTS",
+                |value| {
+                    value
+                        .parse()
+                        .wrap_err_with(|| format!("invalid value for RETRY_ATTEMPTS: {value:?}"))
+                },
+                profile.retry_attempts,
+                Some(DEFAULT_ATTEMPTS),
+            )?
+            .unwrap_or(DEFAULT_ATTEMPTS);
+        // checked after resolving so that a profile can't set it to 0 either
+        if retry_attempts == 0 {
+            return Err(eyre!("invalid value for RETRY_ATTEMPTS: 0").suggestion(
+                "set it, or `retry-attempts` in the selected profile, to 1 or more, 1 disables \
+                 retries",
+            ));
+        }
+        let retry_backoff_ms = resolver
+            .resolve(
+                "RETRY_BACKOFF_MS",
+                |value| {
+                    value
+                        .parse()
+                        .wrap_err_with(|| format!("invalid value for RETRY_BACKOFF_MS: {value:?}"))
+                },
+                profile.retry_backoff_ms,
+                Some(DEFAULT_BACKOFF.as_millis() as u64),
+            )?
+            .unwrap_or_default();
+        let retry_on = resolver
+            .resolve(
+                "RETRY_ON",
+                str::parse,
+                profile.retry_on,
+                Some(ErrorClasses::default()),
+            )?
+            .unwrap_or_default();
+
+        let local_node = match target_chain {
+            TargetChain::Local => {
+                resolver.resolve_local_node(profile.local_node.unwrap_or_default())?
+            }
+            _ => LocalNodeConfig::default(),
+        };
+
+        Ok(Self {
+            profile: name,
+            target_chain,
+            node_url,
+            deploy_config: DeployConfig {
+                force_deploy,
+                seed: deploy_seed,
+                strategy: deploy_strategy.with_blob_size(blob_strategy),
+                existing: existing_contracts,
+                registry: registry.map(Into::into),
+            },
+            cleanup_registry: cleanup_registry.map(Into::into),
+            baseline: BaselineConfig {
+                mode: baseline_mode,
+                path: baseline::baseline_path(baseline_dir, target_chain),
+            },
+            report_dir: report_dir.map(Into::into),
+            retry: RetryPolicy {
+                attempts: retry_attempts,
+                backoff: Duration::from_millis(retry_backoff_ms),
+                retry_on,
+            },
+            local_node,
+            settings: resolver.settings,
+        })
+    }
+
+    pub async fn setup(&self) -> Result<Setup> {
+        let report = self.report()?;
+        retry::set_policy(self.retry.clone());
+        let (wallet, key) = self.chain()?.wallet().await?;
+        cleanup::set_path(self.cleanup_registry.clone());
+
+        Ok(Setup {
+            wallet,
+            deploy_config: self.deploy_config.clone(),
+            baseline: self.baseline.clone(),
+            report_dir: self.report_dir.clone(),
+            report,
+            master_key: MasterKey(key),
+        })
+    }
+
+    pub fn report(&self) -> Result<ConfigReport> {
+        let chain = self.chain()?;
+
+        let mut settings = self.settings.clone();
+        if let Some(key_env) = chain.key_env() {
+            let envs = KeyEnvs::new(key_env);
+            let key_settings = envs.all().into_iter().filter_map(|(name, secret)| {
+                let source = env_source(name);
+                let value = read_env(name).ok()?;
+                let value = if secret {
+                    "<redacted>".to_string()
+                } else {
+                    value
+                };
+
+                Some(Setting {
+                    name: name.to_string(),
+                    value: Some(value),
+                    source,
+                })
+            });
+            settings.extend(key_settings);
+        }
+
+        let chain = match chain.url() {
+            Some(url) => format!("{chain} ({url})"),
+            None => chain.to_string(),
+        };
+
+        Ok(ConfigReport { chain, settings })
+    }
+
+    /// Remote chains are always shared, the local node only if asked for.
+    pub fn shares_setup(&self) -> bool {
+        match self.target_chain {
+            TargetChain::Local => self.local_node.shared.unwrap_or_default(),
+            _ => true,
+        }
+    }
+
+    pub(crate) fn chain(&self) -> Result<Chain> {
+        let chain = match self.target_chain {
+            TargetChain::Local => Chain::Local(self.local_node.clone()),
+            TargetChain::Devnet => Chain::Devnet,
+            TargetChain::Testnet => Chain::Testnet,
+            TargetChain::Custom => Chain::Custom {
+                url: self.node_url.clone().ok_or_else(|| {
+                    eyre!("no node url given for the custom chain")
+                        .suggestion("set NODE_URL or `node-url` in the selected profile")
+                })?,
+                key_env: "NODE_KEY",
+            },
+        };
+
+        Ok(chain)
+    }
+}
+
+/// Source of an env variable: the process environment or the `.env` file.
+fn env_source(name: &str) -> Source {
+    if load_dotenv().contains(name) {
+        Source::DotEnv
+    } else {
+        Source::Env
+    }
+}
+
+/// The flag standing in for `name` if given, otherwise the env variable, with its source.
+fn read_flag_or_env(flags: &Flags, name: &str) -> Option<(String, Source)> {
+    match flags.get(name) {
+        Some(value) => Some((value.clone(), Source::Flag)),
+        None => read_env(name).ok().map(|value| (value, env_source(name))),
+    }
+}
+
+/// Reports an env-only setting, `default` is shown when it is not set.
+fn env_setting(flags: &Flags, name: &'static str, default: &str) -> Setting {
+    match read_flag_or_env(flags, name) {
+        Some((value, source)) => Setting {
+            name: name.to_string(),
+            value: Some(value),
+            source,
+        },
+        None => Setting {
+            name: name.to_string(),
+            value: (!default.is_empty()).then(|| default.to_string()),
+            source: Source::Default,
+        },
+    }
+}
+
+/// Picks the value of each setting (flag, then env, then profile, then default) and records its
+/// source.
+struct Resolver<'a> {
+    profile: Option<String>,
+    flags: &'a Flags,
+    settings: Vec<Setting>,
+}
+
+impl Resolver<'_> {
+    fn resolve<T: std::fmt::Display>(
+        &mut self,
+        name: &'static str,
+        parse: impl FnOnce(&str) -> Result<T>,
+        from_profile: Option<T>,
+        default: Option<T>,
+    ) -> Result<Option<T>> {
+        let (value, source) = match read_flag_or_env(self.flags, name) {
+            Some((value, source)) => (Some(parse(&value)?), source),
+            None => match (from_profile, &self.profile) {
+                (Some(value), Some(profile)) => (Some(value), Source::Profile(profile.clone())),
+                _ => (default, Source::Default),
+            },
+        };
+
+        self.settings.push(Setting {
+            name: name.to_string(),
+            value: value.as_ref().map(ToString::to_string),
+            source,
+        });
+
+        Ok(value)
+    }
+
+    /// Overrides the value already resolved for `name`.
+    fn replace(&mut self, name: &str, value: &str, source: Source) {
+        if let Some(setting) = self.settings.iter_mut().find(|s| s.name == name) {
+            setting.value = Some(value.to_string());
+            setting.source = source;
+        }
+    }
+
+    fn resolve_bool(&mut self, name: &'static str, from_profile: Option<bool>) -> Result<bool> {
+        let value = self.resolve(
+            name,
+            |value| parse_boolean_env(name, value),
+            from_profile,
+            Some(false),
+        )?;
+
+        Ok(value.unwrap_or_default())
+    }
+
+    fn resolve_local_node(&mut self, profile: LocalNodeConfig) -> Result<LocalNodeConfig> {
+        let parse_u64 = |name: &'static str| {
+            move |value: &str| {
+                value
+                    .parse()
+                    .wrap_err_with(|| format!("invalid value for {name}: {value:?}"))
+            }
+        };
+
+        let shared = self.resolve_bool("LOCAL_SHARED", profile.shared)?;
+
+        Ok(LocalNodeConfig {
+            chain_config: self.resolve(
+                "LOCAL_CHAIN_CONFIG",
+                |value| Ok(value.to_string()),
+                profile.chain_config,
+                None,
+            )?,
+            mirror: self.resolve(
+                "LOCAL_MIRROR",
+                |value| Ok(value.to_string()),
+                profile.mirror,
+                None,
+            )?,
+            consensus_parameters: self.resolve(
+                "LOCAL_CONSENSUS_PARAMETERS",
+                |value| Ok(value.to_string()),
+                profile.consensus_parameters,
+                None,
+            )?,
+            max_gas_per_tx: self.resolve(
+                "LOCAL_MAX_GAS_PER_TX",
+                parse_u64("LOCAL_MAX_GAS_PER_TX"),
+                profile.max_gas_per_tx,
+                None,
+            )?,
+            contract_max_size: self.resolve(
+                "LOCAL_CONTRACT_MAX_SIZE",
+                parse_u64("LOCAL_CONTRACT_MAX_SIZE"),
+                profile.contract_max_size,
+                None,
+            )?,
+            block_time: self.resolve("LOCAL_BLOCK_TIME", str::parse, profile.block_time, None)?,
+            base_asset_id: self.resolve(
+                "LOCAL_BASE_ASSET_ID",
+                |value| {
+                    value
+                        .parse()
+                        .map_err(|e| eyre!("invalid value for LOCAL_BASE_ASSET_ID: {e}"))
+                },
+                profile.base_asset_id,
+                None,
+            )?,
+            shared: Some(shared),
+        })
+    }
+}
@@ -0,0 +1,154 @@
+use color_eyre::{
+    eyre::{bail, eyre},
+    Result,
+};
+use fuel_core_client::client::{types::TransactionType as ClientTransaction, FuelClient};
+use fuels::{
+    accounts::provider::Provider,
+    tx::{field::MintGasPrice, ConsensusParameters, FuelTransaction, Receipt},
+    types::{
+        transaction::{
+            BlobTransaction, CreateTransaction, ScriptTransaction, Transaction, UpgradeTransaction,
+            UploadTransaction,
+        },
+        Bytes32,
+    },
+};
+
+use crate::outcome::{self, TxOutcome};
+
+/// The fee a final transaction should have been charged, worked out from the consensus parameters
+/// and the transaction itself rather than taken from the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpectedFee {
+    /// Gas price of the block that included the transaction.
+    pub gas_price: u64,
+    pub metered_bytes: usize,
+    /// Gas charged before execution: bytes, vm initialization, inputs and metadata.
+    pub min_gas: u64,
+    /// Gas used by the script, zero for other transactions.
+    pub script_gas: u64,
+    pub tip: u64,
+    pub fee: u64,
+}
+
+impl ExpectedFee {
+    /// Computes the fee of the transaction behind `outcome` with the fee parameters and gas costs
+    /// in force for the block that included it, which an upgrade may have changed since.
+    pub async fn of(provider: &Provider, outcome: &TxOutcome) -> Result<Self> {
+        let client = outcome::client(provider)?;
+        let (gas_price, params) = block_fee_inputs(&client, outcome.block_height).await?;
+        let params = &params;
+
+        let tx = known_transaction(&client, &outcome.tx_id).await?;
+        let (metered_bytes, min_gas, tip) = match tx {
+            FuelTransaction::Script(tx) => meter(ScriptTransaction::from(tx), params, gas_price)?,
+            FuelTransaction::Create(tx) => meter(CreateTransaction::from(tx), params, gas_price)?,
+            FuelTransaction::Upload(tx) => meter(UploadTransaction::from(tx), params, gas_price)?,
+            FuelTransaction::Upgrade(tx) => meter(UpgradeTransaction::from(tx), params, gas_price)?,
+            FuelTransaction::Blob(tx) => meter(BlobTransaction::from(tx), params, gas_price)?,
+            FuelTransaction::Mint(_) => bail!("mint transaction {} pays no fee", outcome.tx_id),
+        };
+
+        let script_gas = outcome
+            .receipts
+            .iter()
+            .find_map(|receipt| match receipt {
+                Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let gas = u128::from(min_gas + script_gas);
+        let factor = u128::from(params.fee_params().gas_price_factor());
+        let fee = (gas * u128::from(gas_price)).div_ceil(factor) + u128::from(tip);
+
+        Ok(Self {
+            gas_price,
+            metered_bytes,
+            min_gas,
+            script_gas,
+            tip,
+            fee: fee.try_into()?,
+        })
+    }
+
+    /// Errors with the breakdown of the expected fee unless the node charged it, give or take
+    /// `tolerance`.
+    pub async fn assert_matches(
+        provider: &Provider,
+        outcome: &TxOutcome,
+        tolerance: u64,
+    ) -> Result<Self> {
+        let expected = Self::of(provider, outcome).await?;
+
+        if expected.fee.abs_diff(outcome.total_fee) > tolerance {
+            bail!(
+                "transaction {} was charged {} but {} was expected (±{tolerance}): {expected:?}",
+                outcome.tx_id,
+                outcome.total_fee,
+                expected.fee
+            );
+        }
+
+        Ok(expected)
+    }
+}
+
+/// Metered bytes, min gas and tip.
+fn meter<T: Transaction>(
+    tx: T,
+    consensus_parameters: &ConsensusParameters,
+    gas_price: u64,
+) -> Result<(usize, u64, u64)> {
+    let fee = tx
+        .fee_checked_from_tx(consensus_parameters, gas_price)
+        .ok_or_else(|| eyre!("fee of the transaction overflows"))?;
+
+    Ok((
+        tx.metered_bytes_size(),
+        fee.min_gas(),
+        tx.tip().unwrap_or_default(),
+    ))
+}
+
+async fn known_transaction(client: &FuelClient, tx_id: &Bytes32) -> Result<FuelTransaction> {
+    match client.transaction(tx_id).await? {
+        Some(response) => match response.transaction {
+            ClientTransaction::Known(tx) => Ok(tx),
+            ClientTransaction::Unknown => bail!("transaction {tx_id} is of an unknown type"),
+        },
+        None => Err(outcome::TxStatusError::NotFound { tx_id: *tx_id }.into()),
+    }
+}
+
+/// Gas price and consensus parameters of a block. The gas price is read from the mint transaction
+/// that closes every block.
+async fn block_fee_inputs(
+    client: &FuelClient,
+    block_height: u32,
+) -> Result<(u64, ConsensusParameters)> {
+    let block = client
+        .block_by_height(block_height.into())
+        .await?
+        .ok_or_else(|| eyre!("block {block_height} not found"))?;
+    let mint_id = block
+        .transactions
+        .last()
+        .ok_or_else(|| eyre!("block {block_height} has no transactions"))?;
+
+    let gas_price = match known_transaction(client, mint_id).await? {
+        FuelTransaction::Mint(mint) => *mint.gas_price(),
+        _ => bail!("the last transaction of block {block_height} is not a mint"),
+    };
+
+    let version = block.header.consensus_parameters_version;
+    let params = client
+        .consensus_parameters(version.try_into()?)
+        .await?
+        .ok_or_else(|| {
+            eyre!("consensus parameters version {version} of block {block_height} not found")
+        })?;
+
+    Ok((gas_price, params))
+}
@@ -1,176 +1,468 @@
-#[cfg(feature = "fuels_lts_70")]
-mod fuels_lts_70_overrides {
-    use std::cmp::min;
-
-    use color_eyre::{eyre::eyre, Result};
-    use fuel_core_client::client::types::TransactionStatus;
-    use fuels::{
-        accounts::{provider::Provider, wallet::WalletUnlocked},
-        client::FuelClient,
-        core::constants::WORD_SIZE,
-        programs::contract::{Contract, LoadConfiguration},
-        types::{bech32::Bech32ContractId, transaction::TxPolicies, Bytes32},
-    };
-    use rand::Rng;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result, Section,
+};
+use fuels::{
+    accounts::{provider::Provider, Account, ViewOnlyAccount},
+    core::Configurables,
+    crypto::Hasher,
+    programs::{
+        contract::{Contract, LoadConfiguration, Regular, StorageConfiguration},
+        DEFAULT_MAX_FEE_ESTIMATION_TOLERANCE,
+    },
+    tx::StorageSlot,
+    types::{
+        bech32::{Bech32Address, Bech32ContractId},
+        output::Output,
+        transaction::TxPolicies,
+        transaction_builders::{
+            BuildableTransaction, CreateTransactionBuilder, ScriptTransactionBuilder,
+        },
+        AssetId, Bytes32, ContractId,
+    },
+};
+use rand::Rng;
+
+use crate::{
+    compat::TransactionCompat,
+    json_file::JsonFile,
+    outcome::TxOutcome,
+    registry::{self, Deployment, DeploymentKey, DeploymentKind, Registry},
+    retry,
+    setup::{BlobStrategy, DeployConfig, DeployStrategy, Wallet},
+    wallets,
+};
 
-    use crate::setup::DeployConfig;
+/// Deploys `contract_bin` as a loader contract, its code split into blobs according to
+/// `blob_strategy`.
+pub async fn deploy_blobbed(
+    contract_bin: &str,
+    wallet: &Wallet,
+    options: &DeployOptions,
+    salt: [u8; 32],
+    blob_strategy: BlobStrategy,
+) -> Result<Deployed> {
+    let contract_size = std::fs::metadata(contract_bin)?.len();
+    let max_contract_size = wallet
+        .try_provider()?
+        .chain_info()
+        .await?
+        .consensus_parameters
+        .contract_params()
+        .contract_max_size();
+    let words_per_blob = blob_strategy.words_per_blob(contract_size, max_contract_size)?;
+    let (contract, storage_slots) = options.load(contract_bin, salt)?;
+    let loader = contract.convert_to_loader(words_per_blob)?;
 
-    #[allow(async_fn_in_trait)]
-    pub trait ProviderExt {
-        async fn get_tx_total_fee(&self, id: &Bytes32) -> Result<Option<u64>>;
+    let contract_id = loader.contract_id().into();
+    if wallet.try_provider()?.contract_exists(&contract_id).await? {
+        return Ok(Deployed::existing(contract_id));
     }
 
-    impl ProviderExt for Provider {
-        async fn get_tx_total_fee(&self, id: &fuels_lts_70::types::Bytes32) -> Result<Option<u64>> {
-            let client = FuelClient::new(self.url()).map_err(|e| eyre!(e.to_string()))?;
+    let blobs_uploaded = missing_blobs(wallet.try_provider()?, &loader.blob_ids()).await?;
+    let blob_ids = loader.blob_ids().into_iter().map(Bytes32::from).collect();
+    let loader = loader.upload_blobs(wallet, TxPolicies::default()).await?;
+    let outcome = create(
+        wallet,
+        loader.code(),
+        loader.contract_id(),
+        loader.state_root(),
+        salt,
+        storage_slots,
+    )
+    .await?;
+
+    Ok(Deployed {
+        contract_id,
+        blob_ids,
+        blobs_uploaded,
+        outcome: Some(outcome),
+        newly_deployed: true,
+    })
+}
 
-            let tx = client.transaction(id).await?.unwrap();
+pub async fn deploy_normal(
+    contract_bin: &str,
+    wallet: &Wallet,
+    options: &DeployOptions,
+    salt: [u8; 32],
+) -> Result<Deployed> {
+    let (contract, storage_slots) = options.load(contract_bin, salt)?;
 
-            let fee = match tx.status {
-                TransactionStatus::Success { total_fee, .. }
-                | TransactionStatus::Failure { total_fee, .. } => Some(total_fee),
-                _ => None,
-            };
+    let contract_id = contract.contract_id().into();
+    if wallet.try_provider()?.contract_exists(&contract_id).await? {
+        return Ok(Deployed::existing(contract_id));
+    }
+
+    let outcome = create(
+        wallet,
+        contract.code(),
+        contract.contract_id(),
+        contract.state_root(),
+        salt,
+        storage_slots,
+    )
+    .await?;
+
+    Ok(Deployed {
+        contract_id,
+        blob_ids: vec![],
+        blobs_uploaded: 0,
+        outcome: Some(outcome),
+        newly_deployed: true,
+    })
+}
 
-            Ok(fee)
+/// What `deploy`, `deploy_blobbed` and `deploy_normal` did.
+#[derive(Debug, Clone)]
+pub struct Deployed {
+    pub contract_id: Bech32ContractId,
+    /// Empty unless deployed as a loader.
+    pub blob_ids: Vec<Bytes32>,
+    /// How many of `blob_ids` weren't on chain yet.
+    pub blobs_uploaded: usize,
+    /// Of the transaction creating the contract, `None` if it was already on chain.
+    pub outcome: Option<TxOutcome>,
+    /// `false` if the contract was already on chain.
+    pub newly_deployed: bool,
+}
+
+impl Deployed {
+    fn existing(contract_id: Bech32ContractId) -> Self {
+        Self {
+            contract_id,
+            blob_ids: vec![],
+            blobs_uploaded: 0,
+            outcome: None,
+            newly_deployed: false,
         }
     }
-    pub async fn deploy(
-        wallet: &WalletUnlocked,
-        deploy_config: DeployConfig,
-        contract_bin: &str,
-    ) -> color_eyre::Result<Bech32ContractId> {
-        let salt: [u8; 32] = if deploy_config.force_deploy {
-            rand::rng().random()
-        } else {
-            [0; 32]
-        };
+}
 
-        let contract_id = if deploy_config.deploy_in_blobs {
-            deploy_blobbed(contract_bin, wallet, salt).await?
-        } else {
-            deploy_normal(contract_bin, wallet, salt).await?
-        };
+/// What `Contract::deploy` does, but the tx id is kept so that the outcome can be reported under
+/// every SDK version.
+async fn create(
+    wallet: &Wallet,
+    code: Vec<u8>,
+    contract_id: ContractId,
+    state_root: Bytes32,
+    salt: [u8; 32],
+    storage_slots: Vec<StorageSlot>,
+) -> Result<TxOutcome> {
+    let provider = wallet.try_provider()?;
+    let mut tb = CreateTransactionBuilder::prepare_contract_deployment(
+        code,
+        contract_id,
+        state_root,
+        salt.into(),
+        storage_slots,
+        TxPolicies::default(),
+    )
+    .with_max_fee_estimation_tolerance(DEFAULT_MAX_FEE_ESTIMATION_TOLERANCE);
+    wallet.add_witnesses(&mut tb)?;
+    wallet.adjust_for_fee(&mut tb, 0).await?;
+
+    let tx = tb.build(provider).await?;
+    let tx_id = tx.tx_id(provider).await?;
+
+    retry::policy()
+        .submit(
+            provider,
+            &tx_id,
+            &format!("deployment of {contract_id:#x}"),
+            || async {
+                let status = provider
+                    .send_transaction_and_await_commit(tx.clone())
+                    .await?;
+                Ok(status.check(None)?)
+            },
+        )
+        .await
+}
+
+/// How many distinct blobs out of `blob_ids` aren't on chain.
+async fn missing_blobs(provider: &Provider, blob_ids: &[[u8; 32]]) -> Result<usize> {
+    let mut missing = 0;
+    for (i, blob_id) in blob_ids.iter().enumerate() {
+        if blob_ids[..i].contains(blob_id) {
+            continue;
+        }
 
-        Ok(contract_id)
+        let exists = retry::policy()
+            .query("blob query", || async {
+                Ok(provider.blob_exists(*blob_id).await?)
+            })
+            .await?;
+        if !exists {
+            missing += 1;
+        }
     }
 
-    pub async fn deploy_blobbed(
-        contract_bin: &str,
-        wallet: &WalletUnlocked,
-        salt: [u8; 32],
-    ) -> Result<Bech32ContractId> {
-        let contract_size = std::fs::metadata(contract_bin)?.len();
-        let max_contract_size = wallet
-            .provider()
-            .unwrap()
-            .chain_info()
-            .await?
-            .consensus_parameters
-            .contract_params()
-            .contract_max_size();
-        const NUM_BLOBS: u64 = 3;
-        let blob_size = min(
-            contract_size / WORD_SIZE as u64 / NUM_BLOBS,
-            max_contract_size,
+    Ok(missing)
+}
+
+/// How to instantiate a contract binary, so that variants of a contract can be deployed without
+/// recompiling it.
+#[derive(Debug, Clone, Default)]
+pub struct DeployOptions {
+    /// Storage slots JSON to initialize the contract with. Defaults to the
+    /// `<name>-storage_slots.json` forc writes next to the binary.
+    pub storage_slots: Option<PathBuf>,
+    /// Applied on top of the storage slots, replacing those with the same key.
+    pub storage_overrides: Vec<StorageSlot>,
+    /// Encoded configurables, e.g. `MyContractConfigurables::default().with_x(1)?.into()`.
+    pub configurables: Configurables,
+    /// Name the salt is derived from, defaults to the name of the running test. Tests wanting
+    /// several instances of the same contract give each one a name.
+    pub instance: Option<String>,
+}
+
+impl DeployOptions {
+    /// The slots the contract at `contract_bin` starts with: those of `storage_slots`, or of the
+    /// file forc writes next to the binary, with `storage_overrides` applied.
+    pub fn initial_storage(&self, contract_bin: &str) -> Result<Vec<StorageSlot>> {
+        let path = match &self.storage_slots {
+            Some(path) => path.clone(),
+            None => {
+                let bin = Path::new(contract_bin);
+                let name = bin.file_stem().unwrap_or_default().to_string_lossy();
+                bin.with_file_name(format!("{name}-storage_slots.json"))
+            }
+        };
+
+        let contents = std::fs::read_to_string(&path)
+            .wrap_err_with(|| format!("failed to load storage slots from {}", path.display()))?;
+        let slots: Vec<StorageSlot> = serde_json::from_str(&contents)
+            .wrap_err_with(|| format!("invalid storage slots in {}", path.display()))?;
+
+        let mut slots = slots
+            .into_iter()
+            .map(|slot| (*slot.key(), slot))
+            .collect::<BTreeMap<_, _>>();
+        slots.extend(
+            self.storage_overrides
+                .iter()
+                .map(|slot| (*slot.key(), slot.clone())),
         );
-        let bech32_contract_id =
-            Contract::load_from(contract_bin, LoadConfiguration::default().with_salt(salt))?
-                .convert_to_loader(blob_size as usize)?
-                .deploy_if_not_exists(wallet, TxPolicies::default())
-                .await;
-        Ok(bech32_contract_id?)
+
+        Ok(slots.into_values().collect())
     }
 
-    pub async fn deploy_normal(
+    /// The contract at `contract_bin` instantiated with these options, along with its initial
+    /// storage.
+    pub fn load(
+        &self,
         contract_bin: &str,
-        wallet: &WalletUnlocked,
         salt: [u8; 32],
-    ) -> Result<Bech32ContractId> {
-        let bech32_contract_id =
-            Contract::load_from(contract_bin, LoadConfiguration::default().with_salt(salt))?
-                .deploy_if_not_exists(wallet, TxPolicies::default())
-                .await;
-        Ok(bech32_contract_id?)
+    ) -> Result<(Contract<Regular>, Vec<StorageSlot>)> {
+        let storage_slots = self.initial_storage(contract_bin)?;
+        let load_configuration = LoadConfiguration::default()
+            .with_storage_configuration(StorageConfiguration::new(false, storage_slots.clone()))
+            .with_configurables(self.configurables.clone())
+            .with_salt(salt);
+        let contract = Contract::load_from(contract_bin, load_configuration)
+            .wrap_err_with(|| format!("failed to load contract binary {contract_bin}"))?;
+
+        Ok((contract, storage_slots))
     }
 }
-#[cfg(feature = "fuels_lts_70")]
-pub use fuels_lts_70_overrides::*;
-
-#[cfg(feature = "fuels_71")]
-mod fuels_71_overrides {
-    use std::cmp::min;
-
-    use color_eyre::Result;
-    use fuels::{
-        accounts::wallet::Wallet,
-        core::constants::WORD_SIZE,
-        programs::contract::{Contract, LoadConfiguration},
-        types::{bech32::Bech32ContractId, transaction::TxPolicies},
+
+/// Deploys `contract_bin` according to the deploy strategy unless the instance to use is already
+/// on chain, or another one is listed in `DeployConfig::existing`. The instance is picked by its
+/// salt, see [`salt_for`], so every test gets its own and runs with the same seed reuse them. An
+/// instance found in the deployment registry is reused without looking the code up on chain.
+/// Loaders reusing blobs are always new instances with a random salt.
+pub async fn deploy(
+    wallet: &Wallet,
+    deploy_config: DeployConfig,
+    contract_bin: &str,
+    options: &DeployOptions,
+) -> Result<Deployed> {
+    let provider = wallet.try_provider()?;
+
+    if let Some(contract_id) = deploy_config.existing.get(contract_bin) {
+        let contract_id = Bech32ContractId::from(contract_id);
+        if !provider.contract_exists(&contract_id).await? {
+            return Err(eyre!(
+                "contract {} listed for {contract_bin} not found on {}",
+                ContractId::from(&contract_id),
+                provider.url()
+            )
+            .suggestion("check EXISTING_CONTRACTS and the target chain"));
+        }
+
+        return Ok(Deployed::existing(contract_id));
+    }
+
+    let (kind, blob_size) = match deploy_config.strategy {
+        DeployStrategy::Normal => (DeploymentKind::Normal, None),
+        DeployStrategy::Loader { blob_size } | DeployStrategy::LoaderReusingBlobs { blob_size } => {
+            (DeploymentKind::Loader, Some(blob_size))
+        }
     };
-    use rand::Rng;
 
-    use crate::setup::DeployConfig;
+    let (contract, _) = options.load(contract_bin, [0; 32])?;
+    let bytecode_hash = Hasher::default()
+        .chain(contract.code())
+        .chain(contract.state_root())
+        .finalize();
 
-    pub async fn deploy(
-        wallet: &Wallet,
-        deploy_config: DeployConfig,
-        contract_bin: &str,
-    ) -> color_eyre::Result<Bech32ContractId> {
-        let salt: [u8; 32] = if deploy_config.force_deploy {
-            rand::rng().random()
-        } else {
-            [0; 32]
-        };
+    let instance = match &options.instance {
+        Some(instance) => Some(instance.clone()),
+        None => wallets::current_test_name().ok(),
+    };
+    let (salt, seed) = match deploy_config.strategy {
+        DeployStrategy::LoaderReusingBlobs { .. } => (rand::rng().random(), None),
+        _ => (
+            salt_for(&deploy_config.seed, instance.as_deref(), &bytecode_hash),
+            Some(deploy_config.seed.clone()),
+        ),
+    };
 
-        let contract_id = if deploy_config.deploy_in_blobs {
-            deploy_blobbed(contract_bin, wallet, salt).await?
-        } else {
-            deploy_normal(contract_bin, wallet, salt).await?
+    let key = DeploymentKey {
+        chain_id: provider.consensus_parameters().await?.chain_id().into(),
+        node_url: provider.url().to_string(),
+        bytecode_hash,
+        kind,
+        seed,
+        instance,
+    };
+
+    let recorded = match (&deploy_config.registry, &key.seed) {
+        (Some(path), Some(_)) => Registry::load(path)?.find(&key).cloned(),
+        _ => None,
+    };
+    let deployed = match recorded {
+        // gone if the chain was reset, redeployed below with the same salt
+        Some(recorded)
+            if provider
+                .contract_exists(&recorded.contract_id.into())
+                .await? =>
+        {
+            Deployed {
+                blob_ids: recorded.blob_ids,
+                ..Deployed::existing(recorded.contract_id.into())
+            }
+        }
+        _ => match blob_size {
+            Some(blob_size) => {
+                deploy_blobbed(contract_bin, wallet, options, salt, blob_size).await?
+            }
+            None => deploy_normal(contract_bin, wallet, options, salt).await?,
+        },
+    };
+
+    if let Some(path) = &deploy_config.registry {
+        let now = registry::now();
+        let deployment = Deployment {
+            chain_id: key.chain_id,
+            node_url: key.node_url,
+            contract_bin: contract_bin.to_string(),
+            bytecode_hash: key.bytecode_hash,
+            salt: salt.into(),
+            seed: key.seed,
+            instance: key.instance,
+            kind: key.kind,
+            blob_ids: deployed.blob_ids.clone(),
+            contract_id: (&deployed.contract_id).into(),
+            tx_id: deployed.outcome.as_ref().map(|outcome| outcome.tx_id),
+            deployed_at: deployed.newly_deployed.then_some(now),
+            last_used_at: now,
         };
 
-        Ok(contract_id)
+        Registry::update(path, |registry| registry.record(deployment))?;
     }
 
-    pub async fn deploy_blobbed(
-        contract_bin: &str,
-        wallet: &Wallet,
-        salt: [u8; 32],
-    ) -> Result<Bech32ContractId> {
-        let contract_size = std::fs::metadata(contract_bin)?.len();
-        let max_contract_size = wallet
-            .provider()
-            .chain_info()
-            .await?
-            .consensus_parameters
-            .contract_params()
-            .contract_max_size();
-        const NUM_BLOBS: u64 = 3;
-        let blob_size = min(
-            contract_size / WORD_SIZE as u64 / NUM_BLOBS,
-            max_contract_size,
-        );
-        let bech32_contract_id =
-            Contract::load_from(contract_bin, LoadConfiguration::default().with_salt(salt))?
-                .convert_to_loader(blob_size as usize)?
-                .deploy_if_not_exists(wallet, TxPolicies::default())
-                .await?
-                .contract_id;
-        Ok(bech32_contract_id)
+    Ok(deployed)
+}
+
+/// Salt of the instance of a contract variant (identified by `bytecode_hash`) that `instance`,
+/// usually a test, uses for the given seed.
+pub fn salt_for(seed: &str, instance: Option<&str>, bytecode_hash: &Bytes32) -> [u8; 32] {
+    let mut hasher = Hasher::default();
+    for part in [seed.as_bytes(), instance.unwrap_or_default().as_bytes()] {
+        hasher.input((part.len() as u64).to_be_bytes());
+        hasher.input(part);
     }
+    hasher.input(bytecode_hash);
 
-    pub async fn deploy_normal(
-        contract_bin: &str,
-        wallet: &Wallet,
-        salt: [u8; 32],
-    ) -> Result<Bech32ContractId> {
-        let bech32_contract_id =
-            Contract::load_from(contract_bin, LoadConfiguration::default().with_salt(salt))?
-                .deploy_if_not_exists(wallet, TxPolicies::default())
-                .await?
-                .contract_id;
-        Ok(bech32_contract_id)
+    *hasher.finalize()
+}
+
+/// Sends every asset held by `from` to `to`, paying the fee from the base asset being sent.
+/// Returns the outcome of the transfer, or `None` if there was no base asset to pay for it.
+pub async fn transfer_all(from: &impl Account, to: &Bech32Address) -> Result<Option<TxOutcome>> {
+    let policy = retry::policy();
+    let provider = from.try_provider()?;
+    let consensus_parameters = policy
+        .query("consensus parameters query", || async {
+            Ok(provider.consensus_parameters().await?)
+        })
+        .await?;
+    let base_asset_id = *consensus_parameters.base_asset_id();
+
+    let address = from.address();
+    let held = policy
+        .query(&format!("balances query of {address}"), || async {
+            Ok(from.get_balances().await?)
+        })
+        .await?;
+    let mut balances = vec![];
+    for (asset_id, amount) in held {
+        balances.push((
+            AssetId::from_str(&asset_id).map_err(|e| eyre!(e))?,
+            u64::try_from(amount)?,
+        ));
     }
+
+    if !balances
+        .iter()
+        .any(|(asset_id, amount)| *asset_id == base_asset_id && *amount > 0)
+    {
+        return Ok(None);
+    }
+
+    let mut inputs = vec![];
+    let mut outputs = vec![];
+    for (asset_id, amount) in balances {
+        inputs.extend(
+            policy
+                .query(&format!("coins query of {address}"), || async {
+                    Ok(from
+                        .get_asset_inputs_for_amount(asset_id, amount, None)
+                        .await?)
+                })
+                .await?,
+        );
+        outputs.push(Output::change(to.into(), 0, asset_id));
+    }
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default());
+    from.add_witnesses(&mut tb)?;
+    from.adjust_for_fee(&mut tb, 0).await?;
+
+    let tx = tb.build(provider).await?;
+    let tx_id = tx.tx_id(provider).await?;
+
+    let outcome = policy
+        .submit(
+            provider,
+            &tx_id,
+            &format!("transfer from {address}"),
+            || async {
+                let status = provider
+                    .send_transaction_and_await_commit(tx.clone())
+                    .await?;
+                Ok(status.check(None)?)
+            },
+        )
+        .await?;
+
+    Ok(Some(outcome))
 }
-#[cfg(feature = "fuels_71")]
-pub use fuels_71_overrides::*;
@@ -0,0 +1,35 @@
+//! Hex strings without a `0x` prefix. Used as `#[serde(with = "hex_bytes")]` for bytes that
+//! would be unwieldy as arrays of numbers, e.g. predicate code of a few KiB.
+
+use std::fmt::Write;
+
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+pub fn encode(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+/// `None` unless `hex` is an even number of hex digits, without a `0x` prefix.
+pub fn decode(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&encode(bytes))
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+
+    decode(&hex).ok_or_else(|| D::Error::custom("expected an even number of hex digits"))
+}
@@ -0,0 +1,97 @@
+use std::{
+    fs::{File, OpenOptions},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use color_eyre::{eyre::Context, Result, Section};
+use fd_lock::RwLock;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Tells apart the temporary files of writers within the same process, the pid those of
+/// different processes.
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// State the harness keeps between runs as a pretty-printed JSON file: the deployment registry,
+/// the cleanup registry and the gas baseline.
+pub trait JsonFile: Default + Serialize + DeserializeOwned {
+    /// What the file holds, e.g. "deployment registry", for error messages.
+    const DESCRIPTION: &'static str;
+    /// What to do about a file that doesn't parse.
+    const INVALID_SUGGESTION: &'static str;
+
+    /// Reads the file at `path`. A missing file is treated as an empty one.
+    fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+
+        serde_json::from_str(&contents)
+            .wrap_err_with(|| format!("invalid {} {}", Self::DESCRIPTION, path.display()))
+            .suggestion(Self::INVALID_SUGGESTION)
+    }
+
+    /// Written to a temporary file first so that readers never see a partial file.
+    fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let mut lock = open_lock(path)?;
+        let _guard = lock
+            .write()
+            .wrap_err_with(|| format!("failed to lock {}", path.display()))?;
+
+        write_atomically(path, &serde_json::to_string_pretty(self)?)
+    }
+
+    /// Loads the file at `path`, applies `f` and saves the result. Holds an advisory lock on
+    /// `<path>.lock` throughout, so that concurrent updates, also from other processes, don't
+    /// lose each other's changes.
+    fn update<T>(path: impl AsRef<Path>, f: impl FnOnce(&mut Self) -> T) -> Result<T> {
+        let path = path.as_ref();
+        let mut lock = open_lock(path)?;
+        let _guard = lock
+            .write()
+            .wrap_err_with(|| format!("failed to lock {}", path.display()))?;
+
+        let mut contents = Self::load(path)?;
+        let result = f(&mut contents);
+        write_atomically(path, &serde_json::to_string_pretty(&contents)?)?;
+
+        Ok(result)
+    }
+}
+
+/// Opens `<path>.lock`, creating it and the directory of `path` if needed.
+fn open_lock(path: &Path) -> Result<RwLock<File>> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .wrap_err_with(|| format!("failed to create {}", dir.display()))?;
+    }
+
+    let lock_path = PathBuf::from(format!("{}.lock", path.display()));
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .wrap_err_with(|| format!("failed to open {}", lock_path.display()))?;
+
+    Ok(RwLock::new(file))
+}
+
+/// Writes `contents` to a temporary file of this writer and renames it to `path`.
+fn write_atomically(path: &Path, contents: &str) -> Result<()> {
+    let tmp = PathBuf::from(format!(
+        "{}.{}-{}.tmp",
+        path.display(),
+        std::process::id(),
+        TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    std::fs::write(&tmp, contents)
+        .wrap_err_with(|| format!("failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).wrap_err_with(|| format!("failed to write {}", path.display()))
+}
@@ -0,0 +1,135 @@
+use std::path::PathBuf;
+
+use color_eyre::{
+    eyre::{bail, eyre, Context},
+    Result, Section,
+};
+use fuels::crypto::SecretKey;
+
+use crate::setup::read_env;
+
+/// Same path `forc-wallet` and the SDK use for the first account.
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/1179993420'/0'/0/0";
+
+/// Where the private key of the funded wallet comes from. Given the key env `DEV_KEY`, the
+/// related variables are `DEV_MNEMONIC`, `DEV_DERIVATION_PATH`, `DEV_KEYSTORE` and
+/// `DEV_KEYSTORE_PASSWORD`.
+pub enum KeySource {
+    PrivateKey(String),
+    Mnemonic {
+        phrase: String,
+        path: String,
+    },
+    /// An encrypted (eth-style) keystore file holding either a raw private key, as saved by the
+    /// SDK, or a mnemonic phrase, as saved by `forc-wallet`.
+    Keystore {
+        file: PathBuf,
+        password: String,
+        path: String,
+    },
+}
+
+/// Names of the env variables related to `key_env`.
+pub struct KeyEnvs {
+    pub key: String,
+    pub mnemonic: String,
+    pub derivation_path: String,
+    pub keystore: String,
+    pub keystore_password: String,
+}
+
+impl KeyEnvs {
+    pub fn new(key_env: &str) -> Self {
+        let stem = key_env.strip_suffix("_KEY").unwrap_or(key_env);
+
+        Self {
+            key: key_env.to_string(),
+            mnemonic: format!("{stem}_MNEMONIC"),
+            derivation_path: format!("{stem}_DERIVATION_PATH"),
+            keystore: format!("{stem}_KEYSTORE"),
+            keystore_password: format!("{stem}_KEYSTORE_PASSWORD"),
+        }
+    }
+
+    /// Every variable along with whether its value must be kept out of logs.
+    pub fn all(&self) -> [(&str, bool); 5] {
+        [
+            (&self.key, true),
+            (&self.mnemonic, true),
+            (&self.derivation_path, false),
+            (&self.keystore, false),
+            (&self.keystore_password, true),
+        ]
+    }
+}
+
+impl KeySource {
+    pub fn from_env(key_env: &str) -> Result<Self> {
+        let envs = KeyEnvs::new(key_env);
+
+        let key = read_env(&envs.key).ok();
+        let mnemonic = read_env(&envs.mnemonic).ok();
+        let keystore = read_env(&envs.keystore).ok();
+        let path =
+            read_env(&envs.derivation_path).unwrap_or_else(|_| DEFAULT_DERIVATION_PATH.to_string());
+
+        let source = match (key, mnemonic, keystore) {
+            (Some(key), None, None) => Self::PrivateKey(key),
+            (None, Some(phrase), None) => Self::Mnemonic { phrase, path },
+            (None, None, Some(file)) => Self::Keystore {
+                file: file.into(),
+                password: read_env(&envs.keystore_password)?,
+                path,
+            },
+            (None, None, None) => {
+                return Err(eyre!("no wallet configured").suggestion(format!(
+                    "set one of {}, {} or {} (+ {}), add them in a .env file e.g. {}=abcd...",
+                    envs.key, envs.mnemonic, envs.keystore, envs.keystore_password, envs.key
+                )))
+            }
+            _ => {
+                return Err(eyre!("more than one wallet configured").suggestion(format!(
+                    "set only one of {}, {} or {}",
+                    envs.key, envs.mnemonic, envs.keystore
+                )))
+            }
+        };
+
+        Ok(source)
+    }
+
+    pub fn secret_key(&self) -> Result<SecretKey> {
+        let key = match self {
+            Self::PrivateKey(key) => key.parse().wrap_err("given private key is invalid")?,
+            Self::Mnemonic { phrase, path } => from_mnemonic(phrase, path)?,
+            Self::Keystore {
+                file,
+                password,
+                path,
+            } => {
+                let secret = eth_keystore::decrypt_key(file, password)
+                    .wrap_err_with(|| format!("failed to decrypt keystore {}", file.display()))
+                    .suggestion("check the keystore password")?;
+
+                if let Ok(key) = SecretKey::try_from(secret.as_slice()) {
+                    key
+                } else if let Ok(phrase) = String::from_utf8(secret) {
+                    from_mnemonic(&phrase, path)?
+                } else {
+                    bail!(
+                        "keystore {} holds neither a private key nor a mnemonic phrase",
+                        file.display()
+                    )
+                }
+            }
+        };
+
+        Ok(key)
+    }
+}
+
+fn from_mnemonic(phrase: &str, path: &str) -> Result<SecretKey> {
+    SecretKey::new_from_mnemonic_phrase_with_path(phrase, path)
+        .map_err(|e| eyre!("{e}"))
+        .wrap_err_with(|| format!("failed to derive a key from the mnemonic at path {path}"))
+}
@@ -4,8 +4,27 @@ compile_error!("Features 'fuels_lts_70' and 'fuels_71' cannot be enabled at the
 #[cfg(not(any(feature = "fuels_lts_70", feature = "fuels_71")))]
 compile_error!("You must enable exactly one of the features: 'fuels_lts_70' or 'fuels_71'.");
 
+pub mod baseline;
+pub mod cleanup;
+pub mod compat;
+pub mod config;
+pub mod fees;
 pub mod helpers;
+pub(crate) mod hex_bytes;
+pub mod json_file;
+pub mod keys;
+pub mod liquidity_pool;
+pub mod local_node;
+pub mod outcome;
+pub mod predicates;
+pub mod registry;
+pub mod report;
+pub mod reservations;
+pub mod retry;
+pub mod scenario;
 pub mod setup;
+pub mod suites;
+pub mod wallets;
 
 #[macro_export]
 macro_rules! define_fuels {
@@ -0,0 +1,192 @@
+use std::{future::Future, pin::Pin};
+
+use color_eyre::Result;
+use fuels::{
+    accounts::{provider::Provider, ViewOnlyAccount},
+    macros::abigen,
+    programs::calls::{CallParameters, Execution},
+    types::{transaction_builders::VariableOutputPolicy, AssetId, Bits256, ContractId},
+};
+
+use crate::{
+    cleanup::{self, Holding},
+    helpers::{self, DeployOptions},
+    outcome::{CallResponseExt, TxOutcome},
+    setup::{DeployConfig, Wallet},
+};
+
+/// Binary of the `liquidity_pool` Sway project.
+pub const LIQUIDITY_POOL_BIN: &str = "sway/liquidity_pool/out/release/liquidity_pool.bin";
+
+/// Name the deposits are recorded under in the cleanup registry, see [`reclaim`].
+pub const RECLAIMER: &str = "liquidity_pool";
+
+abigen!(Contract(
+    name = "LiquidityContractBindings",
+    abi = "sway/liquidity_pool/out/release/liquidity_pool-abi.json"
+));
+
+/// Withdraws what the master wallet deposited into the pool at `contract_id`. Built into the
+/// cleanup registry so that deposits of a killed run are reclaimed before any fixture is
+/// deployed again.
+pub fn reclaim(
+    wallet: Wallet,
+    contract_id: ContractId,
+) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'static>> {
+    Box::pin(async move {
+        let instance = LiquidityContractBindings::new(contract_id, wallet);
+        Fixture { instance }.reclaim_any_previous_deposits().await
+    })
+}
+
+pub struct Fixture {
+    instance: LiquidityContractBindings<Wallet>,
+}
+
+#[derive(Debug)]
+pub struct DepositCompleted {
+    pub outcome: TxOutcome,
+    pub event: DepositEvent,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Balances {
+    pub base: u64,
+    pub minted: u64,
+}
+
+impl Fixture {
+    pub async fn deploy(
+        wallet: &Wallet,
+        deploy_config: DeployConfig,
+        options: &DeployOptions,
+    ) -> Result<Self> {
+        let contract_id = helpers::deploy(wallet, deploy_config, LIQUIDITY_POOL_BIN, options)
+            .await?
+            .contract_id;
+
+        let instance = LiquidityContractBindings::new(contract_id, wallet.clone());
+
+        Ok(Self { instance })
+    }
+
+    /// The same pool, deposited into and withdrawn from by `wallet`.
+    pub fn connect(&self, wallet: Wallet) -> Self {
+        Self {
+            instance: LiquidityContractBindings::new(self.instance.contract_id().clone(), wallet),
+        }
+    }
+
+    pub async fn reclaim_any_previous_deposits(&self) -> Result<()> {
+        let balances = self.current_balances().await?;
+
+        if balances.minted > 0 {
+            self.withdraw(balances.minted).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn deposit(&self, amount: u64) -> Result<DepositCompleted> {
+        let call_params = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(self.base_asset_id().await?);
+        cleanup::record(&self.provider(), self.holding()).await?;
+
+        let resp = self
+            .instance
+            .methods()
+            .deposit(self.instance.account().address().into())
+            .call_params(call_params)?
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .call()
+            .await?;
+
+        let event = resp
+            .decode_logs_with_type::<DepositEvent>()?
+            .pop()
+            .expect("should have had an event");
+
+        let outcome = resp.outcome(&self.provider()).await?;
+
+        Ok(DepositCompleted { outcome, event })
+    }
+
+    pub async fn withdraw(&self, amount: u64) -> Result<TxOutcome> {
+        let call_params = CallParameters::default()
+            .with_amount(amount)
+            .with_asset_id(self.minted_asset_id());
+
+        let resp = self
+            .instance
+            .methods()
+            .withdraw(self.instance.account().address().into())
+            .call_params(call_params)?
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .call()
+            .await?;
+
+        if self.current_balances().await?.minted == 0 {
+            cleanup::forget(&self.provider(), &self.holding()).await?;
+        }
+
+        resp.outcome(&self.provider()).await
+    }
+
+    pub async fn base_asset_id(&self) -> Result<AssetId> {
+        Ok(*self
+            .provider()
+            .chain_info()
+            .await?
+            .consensus_parameters
+            .base_asset_id())
+    }
+
+    pub async fn total_deposited_ever(&self) -> Result<u64> {
+        let total_deposited = self
+            .instance
+            .methods()
+            .total_deposited_ever()
+            .simulate(Execution::StateReadOnly)
+            .await?
+            .value;
+
+        Ok(total_deposited)
+    }
+
+    pub async fn current_balances(&self) -> Result<Balances> {
+        let base_asset_id = self.base_asset_id().await?;
+        let base_balance = self
+            .instance
+            .account()
+            .get_asset_balance(&base_asset_id)
+            .await?;
+
+        let minted_asset_id = self.minted_asset_id();
+        let minted_balance = self
+            .instance
+            .account()
+            .get_asset_balance(&minted_asset_id)
+            .await?;
+
+        Ok(Balances {
+            base: base_balance,
+            minted: minted_balance,
+        })
+    }
+
+    fn holding(&self) -> Holding {
+        Holding::Deposit {
+            contract_id: self.instance.contract_id().into(),
+            reclaimer: RECLAIMER.to_string(),
+        }
+    }
+
+    fn provider(&self) -> Provider {
+        self.instance.account().try_provider().unwrap().clone()
+    }
+
+    fn minted_asset_id(&self) -> AssetId {
+        self.instance.contract_id().asset_id(&Bits256::zeroed())
+    }
+}
@@ -0,0 +1,301 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result, Section,
+};
+use fuel_core_chain_config::{ChainConfig, CoinConfig, SnapshotMetadata, StateConfig};
+use fuels::{
+    accounts::provider::Provider,
+    crypto::SecretKey,
+    test_helpers::{FuelService, NodeConfig, Trigger, DEFAULT_COIN_AMOUNT},
+    tx::{ConsensusParameters, ContractParameters, TxParameters},
+    types::{Address, AssetId},
+};
+use serde::Deserialize;
+
+use crate::setup::{address_of, wallet_from_key, Chain, Wallet};
+
+/// Where mirrored consensus parameters are saved unless `consensus-parameters` says otherwise.
+pub const DEFAULT_MIRROR_DIR: &str = "consensus-parameters";
+
+/// Number of base asset coins, of `DEFAULT_COIN_AMOUNT` each, the master wallet starts with. A
+/// single coin would make every transaction wait for the change of the previous one.
+pub const MASTER_WALLET_COINS: u64 = 64;
+
+/// Settings of the node spawned for `target-chain = "local"`, under `[profile.<name>.local-node]`.
+/// Whatever is left unset keeps the value the SDK test helpers use.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct LocalNodeConfig {
+    /// Directory holding a `chain_config.json`, or a full snapshot (`metadata.json` along with the
+    /// genesis state) as written by `fuel-core snapshot`. The settings below are applied on top.
+    pub chain_config: Option<String>,
+    /// A remote chain (`devnet`, `testnet` or the url of a node) whose consensus parameters the
+    /// node should use, to reproduce its limits and fee math. They are fetched once and saved to
+    /// `consensus-parameters`, later runs start offline from the saved file.
+    pub mirror: Option<String>,
+    /// JSON file with the consensus parameters to use, defaults to
+    /// `consensus-parameters/<mirror>.json` when mirroring.
+    pub consensus_parameters: Option<String>,
+    pub max_gas_per_tx: Option<u64>,
+    pub contract_max_size: Option<u64>,
+    pub block_time: Option<BlockTime>,
+    pub base_asset_id: Option<AssetId>,
+    /// Whether the tests of a process share one node instead of each starting its own. Defaults
+    /// to `false`.
+    pub shared: Option<bool>,
+}
+
+/// How often the local node produces blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum BlockTime {
+    /// A block as soon as a transaction comes in.
+    Instant,
+    Interval(Duration),
+}
+
+impl FromStr for BlockTime {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parse = |amount: &str| amount.parse::<u64>().ok();
+
+        let block_time = if s == "instant" {
+            Self::Instant
+        } else if let Some(millis) = s.strip_suffix("ms").and_then(parse) {
+            Self::Interval(Duration::from_millis(millis))
+        } else if let Some(secs) = s.strip_suffix('s').and_then(parse) {
+            Self::Interval(Duration::from_secs(secs))
+        } else {
+            return Err(eyre!("invalid block time: {s}")
+                .suggestion("use 'instant' or a duration such as '500ms' or '2s'"));
+        };
+
+        Ok(block_time)
+    }
+}
+
+impl TryFrom<String> for BlockTime {
+    type Error = color_eyre::Report;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl std::fmt::Display for BlockTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Instant => write!(f, "instant"),
+            Self::Interval(interval) if interval.subsec_millis() == 0 => {
+                write!(f, "{}s", interval.as_secs())
+            }
+            Self::Interval(interval) => write!(f, "{}ms", interval.as_millis()),
+        }
+    }
+}
+
+impl LocalNodeConfig {
+    /// Starts the node with a single wallet funded with `MASTER_WALLET_COINS` coins of the base
+    /// asset. Same wallet `launch_provider_and_get_wallet` gives, but we keep hold of the key.
+    pub(crate) async fn launch(&self) -> Result<(Wallet, SecretKey)> {
+        let mut key = [0; 32];
+        key[31] = 1;
+        let key = SecretKey::try_from(key.as_slice())?;
+
+        let consensus_parameters = self.mirrored_consensus_parameters().await?;
+        let (chain_config, mut state_config) = self.chain_and_state_config(consensus_parameters)?;
+
+        let base_asset_id = *chain_config.consensus_parameters.base_asset_id();
+        let owner = Address::from(&address_of(&key));
+        state_config
+            .coins
+            .extend((0..MASTER_WALLET_COINS).map(|_| CoinConfig {
+                tx_id: rand::random::<[u8; 32]>().into(),
+                owner,
+                amount: DEFAULT_COIN_AMOUNT,
+                asset_id: base_asset_id,
+                ..Default::default()
+            }));
+
+        let node_config = NodeConfig {
+            block_production: match self.block_time.unwrap_or(BlockTime::Instant) {
+                BlockTime::Instant => Trigger::Instant,
+                BlockTime::Interval(block_time) => Trigger::Interval { block_time },
+            },
+            ..NodeConfig::default()
+        };
+
+        let service = FuelService::start(node_config, chain_config, state_config).await?;
+        let address = service.bound_address();
+
+        // the node lives as long as the runtime it was started on
+        tokio::spawn(async move {
+            let _service = service;
+            std::future::pending::<()>().await
+        });
+
+        let provider = Provider::from(address).await?;
+
+        Ok((wallet_from_key(key, provider), key))
+    }
+
+    /// Path of the file holding the consensus parameters, if any are to be used.
+    pub fn consensus_parameters_path(&self) -> Option<PathBuf> {
+        if let Some(path) = &self.consensus_parameters {
+            return Some(path.into());
+        }
+
+        let file_name = self
+            .mirror
+            .as_ref()?
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect::<String>();
+
+        Some(Path::new(DEFAULT_MIRROR_DIR).join(format!("{file_name}.json")))
+    }
+
+    /// Loads the saved consensus parameters, fetching them from the mirrored chain first if they
+    /// weren't saved yet.
+    async fn mirrored_consensus_parameters(&self) -> Result<Option<ConsensusParameters>> {
+        let Some(path) = self.consensus_parameters_path() else {
+            return Ok(None);
+        };
+
+        if !path.exists() {
+            let Some(mirror) = &self.mirror else {
+                return Err(
+                    eyre!("consensus parameters file {} not found", path.display())
+                        .suggestion("set `mirror` to fetch them from a remote chain"),
+                );
+            };
+
+            let url = mirror_url(mirror);
+            save_consensus_parameters(&url, &path)
+                .await
+                .wrap_err_with(|| format!("failed to mirror the consensus parameters of {url}"))?;
+        }
+
+        let contents = std::fs::read_to_string(&path)
+            .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+        let consensus_parameters = serde_json::from_str(&contents)
+            .wrap_err_with(|| format!("invalid consensus parameters in {}", path.display()))
+            .suggestion("delete the file to mirror them again")?;
+
+        Ok(Some(consensus_parameters))
+    }
+
+    fn chain_and_state_config(
+        &self,
+        consensus_parameters: Option<ConsensusParameters>,
+    ) -> Result<(ChainConfig, StateConfig)> {
+        let (mut chain_config, state_config) = match &self.chain_config {
+            Some(dir) => load_chain_config(Path::new(dir))
+                .wrap_err_with(|| format!("failed to load the chain config from {dir}"))
+                .suggestion("point `chain-config` to a directory with a chain_config.json")?,
+            None => (default_chain_config(), empty_state_config()),
+        };
+
+        if let Some(consensus_parameters) = consensus_parameters {
+            chain_config.consensus_parameters = consensus_parameters;
+        }
+
+        let consensus_parameters = &mut chain_config.consensus_parameters;
+        if let Some(max_gas_per_tx) = self.max_gas_per_tx {
+            let tx_params = consensus_parameters
+                .tx_params()
+                .with_max_gas_per_tx(max_gas_per_tx);
+            consensus_parameters.set_tx_params(tx_params);
+        }
+        if let Some(contract_max_size) = self.contract_max_size {
+            let contract_params = consensus_parameters
+                .contract_params()
+                .with_contract_max_size(contract_max_size);
+            consensus_parameters.set_contract_params(contract_params);
+        }
+        if let Some(base_asset_id) = self.base_asset_id {
+            consensus_parameters.set_base_asset_id(base_asset_id);
+        }
+
+        Ok((chain_config, state_config))
+    }
+}
+
+/// Fetches the consensus parameters of the node at `url` and saves them as JSON to `path`.
+pub async fn save_consensus_parameters(
+    url: &str,
+    path: impl AsRef<Path>,
+) -> Result<ConsensusParameters> {
+    let path = path.as_ref();
+    let provider = Provider::connect(url)
+        .await
+        .wrap_err_with(|| format!("failed to connect to {url}"))?;
+    let consensus_parameters = provider.consensus_parameters().await?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&consensus_parameters)?)
+        .wrap_err_with(|| format!("failed to write {}", path.display()))?;
+
+    Ok(consensus_parameters)
+}
+
+fn mirror_url(mirror: &str) -> String {
+    let chain = match mirror {
+        "devnet" => Chain::Devnet,
+        "testnet" => Chain::Testnet,
+        url => return url.to_string(),
+    };
+
+    chain.url().expect("remote chains have an url").to_string()
+}
+
+/// A full snapshot if there is one, otherwise just the chain config.
+fn load_chain_config(dir: &Path) -> Result<(ChainConfig, StateConfig)> {
+    if dir.join("metadata.json").exists() {
+        let metadata = SnapshotMetadata::read(dir).map_err(|e| eyre!("{e:?}"))?;
+        let chain_config =
+            ChainConfig::from_snapshot_metadata(&metadata).map_err(|e| eyre!("{e:?}"))?;
+        let state_config =
+            StateConfig::from_snapshot_metadata(metadata).map_err(|e| eyre!("{e:?}"))?;
+
+        Ok((chain_config, state_config))
+    } else {
+        let chain_config =
+            ChainConfig::load(dir.join("chain_config.json")).map_err(|e| eyre!("{e:?}"))?;
+
+        Ok((chain_config, empty_state_config()))
+    }
+}
+
+/// What `setup_test_provider` uses when not given a chain config: testnet's, with increased tx
+/// size and contract size limits.
+fn default_chain_config() -> ChainConfig {
+    let mut consensus_parameters = ConsensusParameters::default();
+    let _ = consensus_parameters.set_block_transaction_size_limit(10_000_000);
+    consensus_parameters.set_tx_params(TxParameters::default().with_max_size(10_000_000));
+    consensus_parameters
+        .set_contract_params(ContractParameters::default().with_contract_max_size(1_000_000));
+
+    ChainConfig {
+        consensus_parameters,
+        ..ChainConfig::local_testnet()
+    }
+}
+
+/// The wallets of `StateConfig::local_testnet` are dropped, only ours gets funded.
+fn empty_state_config() -> StateConfig {
+    StateConfig {
+        coins: vec![],
+        messages: vec![],
+        ..StateConfig::local_testnet()
+    }
+}
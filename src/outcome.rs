@@ -0,0 +1,249 @@
+use std::time::{Duration, Instant};
+
+use color_eyre::{eyre::eyre, Result};
+use fuel_core_client::client::{types::TransactionStatus, FuelClient};
+use fuels::{
+    accounts::provider::Provider,
+    core::{
+        codec::LogDecoder,
+        traits::{Parameterize, Tokenizable},
+    },
+    programs::responses::CallResponse,
+    tx::Receipt,
+    types::Bytes32,
+};
+
+use crate::retry;
+
+/// What became of a transaction, as reported by the node. Returned for the calls, transfers and
+/// deployments the harness submits so that tests can assert on cost and inclusion.
+#[derive(Debug, Clone)]
+pub struct TxOutcome {
+    pub tx_id: Bytes32,
+    pub block_height: u32,
+    pub status: TxOutcomeStatus,
+    pub total_fee: u64,
+    pub total_gas: u64,
+    pub receipts: Vec<Receipt>,
+    /// Logs decoded with the ABI of the called contract, empty for other transactions.
+    pub logs: Vec<String>,
+    log_decoder: Option<LogDecoder>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxOutcomeStatus {
+    Success,
+    /// Included in a block but reverted.
+    Failure {
+        reason: String,
+    },
+}
+
+impl TxOutcome {
+    /// Queries the node for `tx_id`, which must be final already. See
+    /// [`ProviderExt::await_final_status`] to wait for it.
+    pub async fn fetch(provider: &Provider, tx_id: &Bytes32) -> Result<Self> {
+        let status = query_status(&client(provider)?, tx_id).await?;
+
+        Ok(Self::from_status(*tx_id, status)?)
+    }
+
+    fn from_status(
+        tx_id: Bytes32,
+        status: Option<TransactionStatus>,
+    ) -> std::result::Result<Self, TxStatusError> {
+        let (block_height, status, receipts, total_gas, total_fee) = match status {
+            Some(TransactionStatus::Success {
+                block_height,
+                receipts,
+                total_gas,
+                total_fee,
+                ..
+            }) => (
+                block_height,
+                TxOutcomeStatus::Success,
+                receipts,
+                total_gas,
+                total_fee,
+            ),
+            Some(TransactionStatus::Failure {
+                block_height,
+                reason,
+                receipts,
+                total_gas,
+                total_fee,
+                ..
+            }) => (
+                block_height,
+                TxOutcomeStatus::Failure { reason },
+                receipts,
+                total_gas,
+                total_fee,
+            ),
+            Some(TransactionStatus::Submitted { .. }) => {
+                return Err(TxStatusError::NotFinal { tx_id })
+            }
+            Some(TransactionStatus::SqueezedOut { reason }) => {
+                return Err(TxStatusError::SqueezedOut { tx_id, reason })
+            }
+            None => return Err(TxStatusError::NotFound { tx_id }),
+        };
+
+        Ok(Self {
+            tx_id,
+            block_height: *block_height,
+            status,
+            total_fee,
+            total_gas,
+            receipts,
+            logs: vec![],
+            log_decoder: None,
+        })
+    }
+
+    /// Decodes the logs with the decoder of the called contract.
+    pub fn with_log_decoder(mut self, log_decoder: LogDecoder) -> Self {
+        self.logs = log_decoder
+            .decode_logs(&self.receipts)
+            .filter_succeeded()
+            .into_iter()
+            .map(ToString::to_string)
+            .collect();
+        self.log_decoder = Some(log_decoder);
+
+        self
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.status == TxOutcomeStatus::Success
+    }
+
+    /// Logs of type `T`, empty unless the outcome is that of a call.
+    pub fn decode_logs_with_type<T: Tokenizable + Parameterize + 'static>(&self) -> Result<Vec<T>> {
+        match &self.log_decoder {
+            Some(log_decoder) => Ok(log_decoder.decode_logs_with_type(&self.receipts)?),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+#[allow(async_fn_in_trait)]
+pub trait CallResponseExt {
+    async fn outcome(&self, provider: &Provider) -> Result<TxOutcome>;
+}
+
+impl<D> CallResponseExt for CallResponse<D> {
+    async fn outcome(&self, provider: &Provider) -> Result<TxOutcome> {
+        let tx_id = self
+            .tx_id
+            .ok_or_else(|| eyre!("the call response has no tx id"))?;
+
+        Ok(TxOutcome::fetch(provider, &tx_id)
+            .await?
+            .with_log_decoder(self.log_decoder.clone()))
+    }
+}
+
+/// Why a transaction has no final status to report. Carried by the errors of [`TxOutcome::fetch`]
+/// and [`ProviderExt`], `downcast_ref` them to tell the cases apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxStatusError {
+    /// The node doesn't know the transaction.
+    NotFound { tx_id: Bytes32 },
+    /// Submitted but not included in a block yet.
+    NotFinal { tx_id: Bytes32 },
+    /// Dropped from the tx pool without being included.
+    SqueezedOut { tx_id: Bytes32, reason: String },
+    /// Still not final after waiting `timeout`.
+    Timeout { tx_id: Bytes32, timeout: Duration },
+}
+
+impl std::fmt::Display for TxStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound { tx_id } => write!(f, "transaction {tx_id} not found"),
+            Self::NotFinal { tx_id } => write!(f, "transaction {tx_id} is not final yet"),
+            Self::SqueezedOut { tx_id, reason } => {
+                write!(f, "transaction {tx_id} was squeezed out: {reason}")
+            }
+            Self::Timeout { tx_id, timeout } => {
+                write!(f, "transaction {tx_id} not final after {timeout:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TxStatusError {}
+
+/// How often [`ProviderExt::await_final_status`] asks the node.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Queries about submitted transactions. All but `await_final_status` expect the transaction to be
+/// final already.
+#[allow(async_fn_in_trait)]
+pub trait ProviderExt {
+    /// Polls the node until the transaction succeeds or fails. Transactions the node doesn't know
+    /// yet are waited for as well, until `timeout`.
+    async fn await_final_status(&self, tx_id: &Bytes32, timeout: Duration) -> Result<TxOutcome>;
+    async fn tx_total_fee(&self, tx_id: &Bytes32) -> Result<u64>;
+    async fn tx_gas_used(&self, tx_id: &Bytes32) -> Result<u64>;
+    async fn tx_block_height(&self, tx_id: &Bytes32) -> Result<u32>;
+    async fn tx_receipts(&self, tx_id: &Bytes32) -> Result<Vec<Receipt>>;
+}
+
+impl ProviderExt for Provider {
+    async fn await_final_status(&self, tx_id: &Bytes32, timeout: Duration) -> Result<TxOutcome> {
+        let client = client(self)?;
+        let started = Instant::now();
+
+        loop {
+            let status = query_status(&client, tx_id).await?;
+            let error = match TxOutcome::from_status(*tx_id, status) {
+                Ok(outcome) => return Ok(outcome),
+                Err(error @ TxStatusError::SqueezedOut { .. }) => return Err(error.into()),
+                Err(error) => error,
+            };
+
+            if started.elapsed() >= timeout {
+                let error = match error {
+                    TxStatusError::NotFinal { tx_id } => TxStatusError::Timeout { tx_id, timeout },
+                    other => other,
+                };
+                return Err(error.into());
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn tx_total_fee(&self, tx_id: &Bytes32) -> Result<u64> {
+        Ok(TxOutcome::fetch(self, tx_id).await?.total_fee)
+    }
+
+    async fn tx_gas_used(&self, tx_id: &Bytes32) -> Result<u64> {
+        Ok(TxOutcome::fetch(self, tx_id).await?.total_gas)
+    }
+
+    async fn tx_block_height(&self, tx_id: &Bytes32) -> Result<u32> {
+        Ok(TxOutcome::fetch(self, tx_id).await?.block_height)
+    }
+
+    async fn tx_receipts(&self, tx_id: &Bytes32) -> Result<Vec<Receipt>> {
+        Ok(TxOutcome::fetch(self, tx_id).await?.receipts)
+    }
+}
+
+pub(crate) fn client(provider: &Provider) -> Result<FuelClient> {
+    FuelClient::new(provider.url()).map_err(|e| eyre!("invalid node url {}: {e}", provider.url()))
+}
+
+pub(crate) async fn query_status(
+    client: &FuelClient,
+    tx_id: &Bytes32,
+) -> Result<Option<TransactionStatus>> {
+    retry::policy()
+        .query(&format!("status query of {tx_id}"), || async {
+            Ok(client.transaction(tx_id).await?.map(|tx| tx.status))
+        })
+        .await
+}
@@ -0,0 +1,162 @@
+use color_eyre::{eyre::Context, Result};
+use fuels::{
+    accounts::{predicate::Predicate, ViewOnlyAccount},
+    core::Configurables,
+    macros::abigen,
+    programs::executable::Executable,
+    types::{bech32::Bech32Address, AssetId},
+};
+
+use crate::{
+    cleanup::{self, Holding},
+    compat::TransferCompat,
+    helpers,
+    outcome::TxOutcome,
+    retry,
+    setup::Wallet,
+};
+
+/// Binary of the `open_predicate` Sway project, see [`PredicateOptions::open`].
+pub const OPEN_PREDICATE_BIN: &str = "sway/open_predicate/out/release/open_predicate.bin";
+
+abigen!(Predicate(
+    name = "OpenPredicate",
+    abi = "sway/open_predicate/out/release/open_predicate-abi.json"
+));
+
+/// How the predicate code is put on chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PredicateKind {
+    #[default]
+    Normal,
+    /// A small loader in the transaction, the original code is uploaded as a blob by the funder
+    /// when the fixture is loaded.
+    Loader,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PredicateOptions {
+    pub kind: PredicateKind,
+    /// Encoded predicate data, see the `Encoder` generated by `abigen!`.
+    pub data: Vec<u8>,
+    pub configurables: Configurables,
+}
+
+impl PredicateOptions {
+    /// Options of [`OPEN_PREDICATE_BIN`], which anyone knowing `slot` can spend. Fixtures that
+    /// only need somewhere to hold coins use it, each with a slot of its own so that they don't
+    /// share them.
+    pub fn open(slot: u64) -> Result<Self> {
+        Ok(Self {
+            data: OpenPredicateEncoder::default().encode_data(slot)?,
+            configurables: OpenPredicateConfigurables::default()
+                .with_SLOT(slot)?
+                .into(),
+            ..Self::default()
+        })
+    }
+}
+
+/// A predicate funded by, and swept back to, a wallet of the harness. Spending through it works
+/// with any `Account` API via [`PredicateFixture::predicate`].
+#[derive(Debug)]
+pub struct PredicateFixture {
+    predicate: Predicate,
+    funder: Wallet,
+    base_asset_id: AssetId,
+}
+
+impl PredicateFixture {
+    pub async fn load(
+        predicate_bin: &str,
+        funder: &Wallet,
+        options: PredicateOptions,
+    ) -> Result<Self> {
+        let provider = funder.try_provider()?.clone();
+        let base_asset_id = *provider.consensus_parameters().await?.base_asset_id();
+
+        let PredicateOptions {
+            kind,
+            data,
+            configurables,
+        } = options;
+
+        let predicate = match kind {
+            PredicateKind::Normal => Predicate::load_from(predicate_bin)
+                .with_context(|| format!("failed to load predicate {predicate_bin}"))?
+                .with_configurables(configurables),
+            PredicateKind::Loader => {
+                let loader = Executable::load_from(predicate_bin)
+                    .with_context(|| format!("failed to load predicate {predicate_bin}"))?
+                    .convert_to_loader()?
+                    .with_configurables(configurables);
+                // a no-op if the blob is on chain already
+                loader.upload_blob(funder.clone()).await?;
+
+                Predicate::from_code(loader.code())
+            }
+        }
+        .with_data(data)
+        .with_provider(provider);
+
+        Ok(Self {
+            predicate,
+            funder: funder.clone(),
+            base_asset_id,
+        })
+    }
+
+    pub fn predicate(&self) -> &Predicate {
+        &self.predicate
+    }
+
+    pub fn address(&self) -> &Bech32Address {
+        self.predicate.address()
+    }
+
+    pub async fn balance(&self) -> Result<u64> {
+        retry::policy()
+            .query(&format!("balance query of {}", self.address()), || async {
+                Ok(self
+                    .predicate
+                    .get_asset_balance(&self.base_asset_id)
+                    .await?)
+            })
+            .await
+    }
+
+    /// Sends `amount` of the base asset from the funder to the predicate, which is recorded in
+    /// the cleanup registry until swept.
+    pub async fn fund(&self, amount: u64) -> Result<TxOutcome> {
+        cleanup::record(self.funder.try_provider()?, self.holding()).await?;
+
+        self.funder
+            .transfer_with_outcome(self.address(), amount, self.base_asset_id)
+            .await
+    }
+
+    /// Sends `amount` of the base asset from the predicate to `to`, the fee is paid by the
+    /// predicate as well.
+    pub async fn spend(&self, to: &Bech32Address, amount: u64) -> Result<TxOutcome> {
+        self.predicate
+            .transfer_with_outcome(to, amount, self.base_asset_id)
+            .await
+    }
+
+    /// Sends every asset held by the predicate back to the funder, paying the fee out of the
+    /// predicate's base asset. Returns the outcome of the transfer, if anything was sent. Called
+    /// before funding too, so that coins left behind by a killed run don't skew balances.
+    pub async fn sweep(&self) -> Result<Option<TxOutcome>> {
+        let outcome = helpers::transfer_all(&self.predicate, self.funder.address()).await?;
+        cleanup::forget(self.funder.try_provider()?, &self.holding()).await?;
+
+        Ok(outcome)
+    }
+
+    fn holding(&self) -> Holding {
+        Holding::Predicate {
+            code: self.predicate.code().to_vec(),
+            data: self.predicate.data().to_vec(),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use fuels::types::{Bytes32, ContractId, Salt};
+use serde::{Deserialize, Serialize};
+
+use crate::json_file::JsonFile;
+
+/// Default location of the registry, relative to the project root.
+pub const DEFAULT_REGISTRY_PATH: &str = "deployments.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentKind {
+    Normal,
+    /// Code uploaded as blobs, deployed as a loader contract.
+    Loader,
+}
+
+impl std::fmt::Display for DeploymentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::Loader => write!(f, "loader"),
+        }
+    }
+}
+
+/// What identifies a contract instance worth reusing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeploymentKey {
+    pub chain_id: u64,
+    /// Chains don't always have distinct ids, e.g. devnet and testnet.
+    pub node_url: String,
+    /// sha256 of the code as deployed, configurables applied, and of the initial storage root.
+    pub bytecode_hash: Bytes32,
+    pub kind: DeploymentKind,
+    /// What the salt is derived from, see `helpers::salt_for`.
+    pub seed: Option<String>,
+    pub instance: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Deployment {
+    pub chain_id: u64,
+    pub node_url: String,
+    /// Path of the binary, for humans. Instances are matched on `bytecode_hash`.
+    pub contract_bin: String,
+    pub bytecode_hash: Bytes32,
+    pub salt: Salt,
+    /// What the salt was derived from, see `helpers::deploy`. `None` for random salts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    pub kind: DeploymentKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blob_ids: Vec<Bytes32>,
+    pub contract_id: ContractId,
+    /// `None` if the contract was already on chain when first recorded.
+    pub tx_id: Option<Bytes32>,
+    /// Unix timestamp, `None` if the contract was already on chain when first recorded.
+    pub deployed_at: Option<u64>,
+    /// Unix timestamp of the last run that used the instance.
+    pub last_used_at: u64,
+}
+
+impl Deployment {
+    pub fn key(&self) -> DeploymentKey {
+        DeploymentKey {
+            chain_id: self.chain_id,
+            node_url: self.node_url.clone(),
+            bytecode_hash: self.bytecode_hash,
+            kind: self.kind,
+            seed: self.seed.clone(),
+            instance: self.instance.clone(),
+        }
+    }
+}
+
+/// Every contract instance deployed by the suite, persisted as JSON so that later runs reuse
+/// them and it's known which instance each run exercised.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Registry {
+    pub deployments: Vec<Deployment>,
+}
+
+impl JsonFile for Registry {
+    const DESCRIPTION: &'static str = "deployment registry";
+    const INVALID_SUGGESTION: &'static str =
+        "fix or delete the file, contracts will be redeployed if needed";
+}
+
+impl Registry {
+    /// The most recently used instance matching `key`.
+    pub fn find(&self, key: &DeploymentKey) -> Option<&Deployment> {
+        self.deployments
+            .iter()
+            .filter(|deployment| deployment.key() == *key)
+            .max_by_key(|deployment| deployment.last_used_at)
+    }
+
+    /// Adds `deployment`, or marks it as used if the same instance is already registered.
+    pub fn record(&mut self, deployment: Deployment) {
+        let existing = self.deployments.iter_mut().find(|registered| {
+            registered.contract_id == deployment.contract_id
+                && registered.chain_id == deployment.chain_id
+                && registered.node_url == deployment.node_url
+        });
+
+        match existing {
+            // redeployed with the same salt, e.g. after a chain reset
+            Some(registered) if deployment.deployed_at.is_some() => *registered = deployment,
+            Some(registered) => registered.last_used_at = deployment.last_used_at,
+            None => self.deployments.push(deployment),
+        }
+    }
+
+    /// Removes the deployments matching `predicate` and returns them.
+    pub fn prune(&mut self, mut predicate: impl FnMut(&Deployment) -> bool) -> Vec<Deployment> {
+        let (pruned, kept) = std::mem::take(&mut self.deployments)
+            .into_iter()
+            .partition(|deployment| predicate(deployment));
+        self.deployments = kept;
+
+        pruned
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("after the epoch")
+        .as_secs()
+}
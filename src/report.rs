@@ -0,0 +1,254 @@
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use color_eyre::{eyre::Context, Result};
+use fuels::{accounts::ViewOnlyAccount, types::Bytes32};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    compat::SDK_FEATURE,
+    outcome::{TxOutcome, TxOutcomeStatus},
+    registry,
+    setup::Setup,
+    suites::SuiteRun,
+};
+
+/// The report of the `cargo test`s of this process, see [`record_test`].
+static TEST_RUN: Mutex<Option<RunReport>> = Mutex::new(None);
+
+/// Results of a `fuel-e2e run`, or of the `cargo test`s of a test binary, written as JSON and
+/// JUnit XML by [`RunReport::write`] so that dashboards can follow the health of each chain and
+/// SDK version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunReport {
+    /// The target chain as shown by the configuration report, e.g. `testnet (https://...)`.
+    pub chain: String,
+    pub chain_id: u64,
+    pub node_url: String,
+    /// `fuels_lts_70` or `fuels_71`.
+    pub sdk_feature: String,
+    /// Unix timestamp.
+    pub started_at: u64,
+    pub duration_secs: f64,
+    pub tests: Vec<TestReport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestReport {
+    pub name: String,
+    pub passed: bool,
+    pub duration_secs: f64,
+    pub transactions: Vec<TransactionReport>,
+    /// The error and its causes, outermost first. Empty if the test passed.
+    pub failure: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionReport {
+    pub name: String,
+    pub tx_id: Bytes32,
+    pub block_height: u32,
+    pub success: bool,
+    pub total_gas: u64,
+    pub total_fee: u64,
+}
+
+impl From<&SuiteRun> for TestReport {
+    fn from(run: &SuiteRun) -> Self {
+        let failure = match &run.result {
+            Ok(()) => vec![],
+            Err(err) => err.chain().map(ToString::to_string).collect(),
+        };
+
+        Self {
+            name: run.name.clone(),
+            passed: run.passed(),
+            duration_secs: run.duration.as_secs_f64(),
+            transactions: run
+                .transactions
+                .iter()
+                .map(|(name, outcome)| TransactionReport::new(name, outcome))
+                .collect(),
+            failure,
+        }
+    }
+}
+
+impl TransactionReport {
+    pub fn new(name: &str, outcome: &TxOutcome) -> Self {
+        Self {
+            name: name.to_string(),
+            tx_id: outcome.tx_id,
+            block_height: outcome.block_height,
+            success: outcome.status == TxOutcomeStatus::Success,
+            total_gas: outcome.total_gas,
+            total_fee: outcome.total_fee,
+        }
+    }
+}
+
+impl RunReport {
+    /// Report of `runs`, made against the chain of `setup` from `started_at` on.
+    pub async fn new(setup: &Setup, started_at: u64, runs: &[SuiteRun]) -> Result<Self> {
+        let provider = setup.wallet.try_provider()?;
+
+        Ok(Self {
+            chain: setup.report.chain.clone(),
+            chain_id: provider.consensus_parameters().await?.chain_id().into(),
+            node_url: provider.url().to_string(),
+            sdk_feature: SDK_FEATURE.to_string(),
+            started_at,
+            duration_secs: runs.iter().map(|run| run.duration.as_secs_f64()).sum(),
+            tests: runs.iter().map(TestReport::from).collect(),
+        })
+    }
+
+    pub fn failures(&self) -> usize {
+        self.tests.iter().filter(|test| !test.passed).count()
+    }
+
+    /// Writes `<name>.json` and `<name>.xml` into `dir`, creating it if needed, where the name
+    /// tells apart the runs of different chains, SDK versions, times and processes. Returns both
+    /// paths.
+    pub fn write(&self, dir: impl AsRef<Path>) -> Result<(PathBuf, PathBuf)> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)
+            .wrap_err_with(|| format!("failed to create {}", dir.display()))?;
+
+        let name = format!(
+            "fuel-e2e-{}-{}-{}-{}",
+            self.chain_id,
+            self.sdk_feature,
+            self.started_at,
+            std::process::id()
+        );
+        let json = dir.join(format!("{name}.json"));
+        let xml = dir.join(format!("{name}.xml"));
+
+        std::fs::write(&json, serde_json::to_string_pretty(self)?)
+            .wrap_err_with(|| format!("failed to write {}", json.display()))?;
+        std::fs::write(&xml, self.to_junit())
+            .wrap_err_with(|| format!("failed to write {}", xml.display()))?;
+
+        Ok((json, xml))
+    }
+
+    /// A single `<testsuite>` named after the chain and the SDK feature, one `<testcase>` per
+    /// test. The transactions of a test are listed in its `<system-out>`.
+    pub fn to_junit(&self) -> String {
+        let suite = format!("fuel-e2e {} {}", self.chain_id, self.sdk_feature);
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"fuel-e2e\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+            self.tests.len(),
+            self.failures(),
+            self.duration_secs
+        );
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" time=\"{:.3}\">",
+            escape(&suite),
+            self.tests.len(),
+            self.failures(),
+            self.duration_secs
+        );
+
+        xml.push_str("    <properties>\n");
+        for (name, value) in [
+            ("chain", self.chain.clone()),
+            ("chain_id", self.chain_id.to_string()),
+            ("node_url", self.node_url.clone()),
+            ("sdk_feature", self.sdk_feature.clone()),
+            ("started_at", self.started_at.to_string()),
+        ] {
+            let _ = writeln!(
+                xml,
+                "      <property name=\"{name}\" value=\"{}\"/>",
+                escape(&value)
+            );
+        }
+        xml.push_str("    </properties>\n");
+
+        for test in &self.tests {
+            let _ = writeln!(
+                xml,
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\">",
+                escape(&test.name),
+                escape(&suite),
+                test.duration_secs
+            );
+            if !test.passed {
+                let _ = writeln!(
+                    xml,
+                    "      <failure message=\"{}\">{}</failure>",
+                    escape(test.failure.first().map(String::as_str).unwrap_or_default()),
+                    escape(&test.failure.join("\n"))
+                );
+            }
+            if !test.transactions.is_empty() {
+                let transactions = test
+                    .transactions
+                    .iter()
+                    .map(|tx| {
+                        format!(
+                            "{} {} gas {} fee {}",
+                            tx.name, tx.tx_id, tx.total_gas, tx.total_fee
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                let _ = writeln!(
+                    xml,
+                    "      <system-out>{}</system-out>",
+                    escape(&transactions.join("\n"))
+                );
+            }
+            xml.push_str("    </testcase>\n");
+        }
+
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+/// Adds `run` to the report of the tests of this process and writes it again, so that it is
+/// complete whichever test finishes last. A no-op unless `REPORT_DIR` is set.
+pub async fn record_test(setup: &Setup, run: &SuiteRun) -> Result<()> {
+    let Some(dir) = &setup.report_dir else {
+        return Ok(());
+    };
+
+    // queried up front, the lock can't be held across an await
+    let first = RunReport::new(setup, registry::now(), std::slice::from_ref(run)).await?;
+
+    let mut test_run = TEST_RUN.lock().expect("not poisoned");
+    let report = match test_run.as_mut() {
+        Some(report) => {
+            report.duration_secs += run.duration.as_secs_f64();
+            report.tests.push(TestReport::from(run));
+            report
+        }
+        None => test_run.insert(first),
+    };
+    report.write(dir)?;
+
+    Ok(())
+}
+
+fn escape(text: &str) -> String {
+    text.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+        escaped
+    })
+}
@@ -0,0 +1,139 @@
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use fuels::{
+    accounts::{provider::Provider, Account, ViewOnlyAccount},
+    types::{
+        bech32::Bech32Address, coin_type_id::CoinTypeId, errors::Result, input::Input,
+        transaction_builders::TransactionBuilder, AssetId,
+    },
+};
+
+/// How long a coin stays reserved. Long enough for the transaction spending it to be committed,
+/// after which the node stops returning it anyway. Reservations are never released early: a
+/// failed submission may still have been committed, so the coins of a rejected transaction only
+/// become available again once it elapses.
+pub const RESERVATION_TTL: Duration = Duration::from_secs(60);
+
+/// How long to wait for in-flight transactions to return change before giving up on finding
+/// enough unreserved coins.
+const MAX_WAIT_FOR_COINS: Duration = Duration::from_secs(30);
+
+/// Coins handed out to transactions of this process, shared by every test running in it.
+static RESERVED: LazyLock<Mutex<HashMap<CoinTypeId, Instant>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Held while querying the node so that two concurrent queries can't both pick the same coins
+/// before either got to reserve them.
+static QUERY_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Wraps an account so that coins picked for one transaction are not picked for another one
+/// until the first one had time to be committed. This is what allows concurrent tests to spend
+/// from the same wallet without failing on already spent inputs.
+#[derive(Debug, Clone)]
+pub struct ReservingWallet<A> {
+    inner: A,
+}
+
+impl<A> ReservingWallet<A> {
+    pub fn new(inner: A) -> Self {
+        Self { inner }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+}
+
+/// Ids of all coins currently reserved, dropping the expired reservations along the way.
+pub fn reserved_coins() -> Vec<CoinTypeId> {
+    let mut reserved = RESERVED.lock().expect("not poisoned");
+    reserved.retain(|_, reserved_at| reserved_at.elapsed() < RESERVATION_TTL);
+
+    reserved.keys().cloned().collect()
+}
+
+fn reserve(inputs: &[Input]) {
+    let now = Instant::now();
+    let mut reserved = RESERVED.lock().expect("not poisoned");
+    reserved.extend(coin_ids(inputs).map(|id| (id, now)));
+}
+
+fn coin_ids(inputs: &[Input]) -> impl Iterator<Item = CoinTypeId> + '_ {
+    inputs.iter().filter_map(|input| match input {
+        Input::ResourceSigned { resource } | Input::ResourcePredicate { resource, .. } => {
+            resource.id()
+        }
+        Input::Contract { .. } => None,
+    })
+}
+
+#[async_trait]
+impl<A> ViewOnlyAccount for ReservingWallet<A>
+where
+    A: ViewOnlyAccount + Clone + std::fmt::Debug,
+{
+    fn address(&self) -> &Bech32Address {
+        self.inner.address()
+    }
+
+    fn try_provider(&self) -> Result<&Provider> {
+        self.inner.try_provider()
+    }
+
+    async fn get_asset_inputs_for_amount(
+        &self,
+        asset_id: AssetId,
+        amount: u64,
+        excluded_coins: Option<Vec<CoinTypeId>>,
+    ) -> Result<Vec<Input>> {
+        let started = Instant::now();
+        let mut backoff = Duration::from_millis(100);
+
+        loop {
+            let guard = QUERY_LOCK.lock().await;
+
+            let reserved = reserved_coins();
+            let any_reserved = !reserved.is_empty();
+
+            let mut excluded = excluded_coins.clone().unwrap_or_default();
+            excluded.extend(reserved);
+
+            match self
+                .inner
+                .get_asset_inputs_for_amount(asset_id, amount, Some(excluded))
+                .await
+            {
+                Ok(inputs) => {
+                    reserve(&inputs);
+                    return Ok(inputs);
+                }
+                // the coins we need might be the change of a transaction still in flight
+                Err(_) if any_reserved && started.elapsed() < MAX_WAIT_FOR_COINS => {
+                    drop(guard);
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(Duration::from_secs(2));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<A> Account for ReservingWallet<A>
+where
+    A: Account + Clone + std::fmt::Debug,
+{
+    fn add_witnesses<Tb: TransactionBuilder>(&self, tb: &mut Tb) -> Result<()> {
+        self.inner.add_witnesses(tb)
+    }
+}
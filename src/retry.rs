@@ -0,0 +1,281 @@
+use std::{future::Future, str::FromStr, sync::Mutex, time::Duration};
+
+use color_eyre::{
+    eyre::{eyre, Report},
+    Result, Section,
+};
+use fuels::{accounts::provider::Provider, types::Bytes32};
+use serde::Deserialize;
+
+use crate::outcome::{self, ProviderExt, TxOutcome, TxStatusError};
+
+/// Attempts made by default, the first one included.
+pub const DEFAULT_ATTEMPTS: u32 = 3;
+/// Wait before the second attempt, doubled for every attempt after it.
+pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+/// The backoff stops doubling once it reaches this.
+pub const MAX_BACKOFF: Duration = Duration::from_secs(10);
+/// How long a resubmission waits for a transaction the node already knows to become final.
+const KNOWN_TX_TIMEOUT: Duration = Duration::from_secs(60);
+
+static POLICY: Mutex<Option<RetryPolicy>> = Mutex::new(None);
+
+/// Transient failures worth another attempt. Told apart by the message of the error, since the
+/// SDK and the client flatten the transport errors into strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorClass {
+    /// The request or the connection timed out.
+    Timeout,
+    /// The connection could not be established or was dropped.
+    Connection,
+    /// 502, 503 and 504 responses, e.g. a public endpoint behind a restarting load balancer.
+    Unavailable,
+    /// 429 responses.
+    RateLimited,
+}
+
+impl ErrorClass {
+    pub const ALL: [Self; 4] = [
+        Self::Timeout,
+        Self::Connection,
+        Self::Unavailable,
+        Self::RateLimited,
+    ];
+
+    /// Lowercase fragments of the messages of each class. Checked in this order, so that a 504
+    /// counts as unavailable rather than as a timeout.
+    const PATTERNS: [(Self, &'static [&'static str]); 4] = [
+        (
+            Self::RateLimited,
+            &["server returned 429", "too many requests"],
+        ),
+        (
+            Self::Unavailable,
+            &[
+                "server returned 502",
+                "server returned 503",
+                "server returned 504",
+                "bad gateway",
+                "service unavailable",
+            ],
+        ),
+        (
+            Self::Timeout,
+            &["timed out", "timeout", "deadline has elapsed"],
+        ),
+        (
+            Self::Connection,
+            &[
+                "error sending request",
+                "connection refused",
+                "connection reset",
+                "connection closed",
+                "broken pipe",
+                "dns error",
+                "unexpected eof",
+            ],
+        ),
+    ];
+
+    /// The class of `err`, `None` if it isn't transient. Errors about the status of a
+    /// transaction, such as a [`TxStatusError::Timeout`], are never transient here: waiting for
+    /// a transaction is up to the caller.
+    pub fn of(err: &Report) -> Option<Self> {
+        if err.chain().any(|cause| cause.is::<TxStatusError>()) {
+            return None;
+        }
+
+        let message = err
+            .chain()
+            .map(|cause| cause.to_string().to_lowercase())
+            .collect::<Vec<_>>()
+            .join(": ");
+
+        Self::PATTERNS
+            .iter()
+            .find(|(_, patterns)| patterns.iter().any(|pattern| message.contains(pattern)))
+            .map(|(class, _)| *class)
+    }
+}
+
+impl FromStr for ErrorClass {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "timeout" => Ok(Self::Timeout),
+            "connection" => Ok(Self::Connection),
+            "unavailable" => Ok(Self::Unavailable),
+            "rate-limited" => Ok(Self::RateLimited),
+            other => Err(eyre!("invalid error class: {other}")
+                .suggestion("use 'timeout', 'connection', 'unavailable' or 'rate-limited'")),
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout => write!(f, "timeout"),
+            Self::Connection => write!(f, "connection"),
+            Self::Unavailable => write!(f, "unavailable"),
+            Self::RateLimited => write!(f, "rate-limited"),
+        }
+    }
+}
+
+/// The classes retried, written comma separated, e.g. `timeout,unavailable`. Empty to retry
+/// nothing.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct ErrorClasses(pub Vec<ErrorClass>);
+
+impl Default for ErrorClasses {
+    fn default() -> Self {
+        Self(ErrorClass::ALL.to_vec())
+    }
+}
+
+impl FromStr for ErrorClasses {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|class| !class.is_empty())
+            .map(str::parse)
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+}
+
+impl std::fmt::Display for ErrorClasses {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let classes = self.0.iter().map(ToString::to_string).collect::<Vec<_>>();
+        write!(f, "{}", classes.join(","))
+    }
+}
+
+/// How the harness rides out transient failures of the node: connecting to it, the queries it
+/// makes and the transfers it submits through [`RetryPolicy::submit`]. Calls and transfers
+/// submitted through the SDK are not retried, since a lost response doesn't tell whether they
+/// went through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// At least one.
+    pub attempts: u32,
+    pub backoff: Duration,
+    pub retry_on: ErrorClasses,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: DEFAULT_ATTEMPTS,
+            backoff: DEFAULT_BACKOFF,
+            retry_on: ErrorClasses::default(),
+        }
+    }
+}
+
+/// The policy used from now on by the harness.
+pub fn set_policy(policy: RetryPolicy) {
+    *POLICY.lock().expect("not poisoned") = Some(policy);
+}
+
+/// The policy set by [`set_policy`], the default one if none was.
+pub fn policy() -> RetryPolicy {
+    POLICY
+        .lock()
+        .expect("not poisoned")
+        .clone()
+        .unwrap_or_default()
+}
+
+impl RetryPolicy {
+    /// Wait before attempt `attempt + 1`, counting from 1.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(MAX_BACKOFF)
+    }
+
+    /// Whether `err`, raised by attempt `attempt`, is worth another one.
+    fn retries(&self, err: &Report, attempt: u32) -> Option<ErrorClass> {
+        if attempt >= self.attempts {
+            return None;
+        }
+
+        ErrorClass::of(err).filter(|class| self.retry_on.0.contains(class))
+    }
+
+    /// Runs `query` until it succeeds, fails with an error not worth retrying or runs out of
+    /// attempts. Only for what can safely run twice. `what` names it in the log of each retry.
+    pub async fn query<T, F, Fut>(&self, what: &str, mut query: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match query().await {
+                Ok(value) => return Ok(value),
+                Err(err) => match self.retries(&err, attempt) {
+                    Some(class) => self.wait(what, class, attempt, &err).await,
+                    None => return Err(err),
+                },
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Submits the transaction `tx_id` with `send`, which returns once it is final. After a
+    /// transient failure the node is asked about `tx_id` first: a transaction it knows is waited
+    /// for instead of being sent again, so a submission whose response got lost is never
+    /// submitted twice.
+    pub async fn submit<F, Fut>(
+        &self,
+        provider: &Provider,
+        tx_id: &Bytes32,
+        what: &str,
+        mut send: F,
+    ) -> Result<TxOutcome>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let err = match send().await {
+                Ok(()) => return TxOutcome::fetch(provider, tx_id).await,
+                Err(err) => err,
+            };
+            let Some(class) = self.retries(&err, attempt) else {
+                return Err(err);
+            };
+
+            let client = outcome::client(provider)?;
+            if outcome::query_status(&client, tx_id).await?.is_some() {
+                eprintln!(
+                    "{what} failed ({class}) but the node knows transaction {tx_id}, \
+                     waiting for it instead of resubmitting: {err:#}"
+                );
+                return provider.await_final_status(tx_id, KNOWN_TX_TIMEOUT).await;
+            }
+
+            self.wait(what, class, attempt, &err).await;
+            attempt += 1;
+        }
+    }
+
+    async fn wait(&self, what: &str, class: ErrorClass, attempt: u32, err: &Report) {
+        let delay = self.delay(attempt);
+        eprintln!(
+            "{what} failed ({class}), retrying in {delay:?} (attempt {} of {}): {err:#}",
+            attempt + 1,
+            self.attempts
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
@@ -0,0 +1,866 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use color_eyre::{
+    eyre::{bail, ensure, eyre, Context},
+    Result, Section,
+};
+use fuel_abi_types::{
+    abi::unified_program::{UnifiedProgramABI, UnifiedTypeDeclaration},
+    error_codes::{FAILED_REQUIRE_SIGNAL, REVERT_WITH_LOG_SIGNAL},
+};
+use fuels::{
+    accounts::ViewOnlyAccount,
+    core::codec::{encode_fn_selector, ABIDecoder, DecoderConfig, EncoderConfig, LogDecoder},
+    programs::calls::{receipt_parser::ReceiptParser, CallHandler, CallParameters, ContractCall},
+    tx::Receipt,
+    types::{
+        bech32::{Bech32Address, Bech32ContractId},
+        errors::{transaction::Reason, Error},
+        param_types::ParamType,
+        transaction_builders::VariableOutputPolicy,
+        Address, AssetId, Bytes32, ContractId, StaticStringToken, Token, U256,
+    },
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    baseline::Costs,
+    compat::TransferCompat,
+    helpers::{self, DeployOptions},
+    hex_bytes,
+    outcome::{CallResponseExt, TxOutcome},
+    setup::{Setup, Wallet},
+    wallets::FreshWallet,
+};
+
+/// Where [`Scenario::load_dir`] looks by default, relative to the project root.
+pub const DEFAULT_SCENARIO_DIR: &str = "scenarios";
+
+/// A test case written as TOML rather than Rust: contracts to deploy, calls to make with JSON
+/// arguments, transfers and the results to expect. Arguments and results are encoded with the
+/// `-abi.json` forc writes next to the contract binary.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    /// Defaults to the name of the file.
+    #[serde(default)]
+    pub name: String,
+    /// One line on what the scenario checks, listed by `fuel-e2e list`.
+    #[serde(default)]
+    pub description: String,
+    /// Fresh wallets the scenario uses, with the base asset each is funded with. Swept back to
+    /// the master wallet once the scenario is over.
+    #[serde(default)]
+    pub wallets: BTreeMap<String, u64>,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "step", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum Step {
+    /// Deploys `bin` with the deploy strategy of the setup.
+    Deploy {
+        bin: String,
+        /// Defaults to the `<name>-abi.json` next to `bin`.
+        abi: Option<PathBuf>,
+        /// How later steps refer to the contract, defaults to the name of `bin`.
+        name: Option<String>,
+    },
+    Call {
+        contract: String,
+        method: String,
+        /// One JSON value per argument, see [`token_from_json`].
+        #[serde(default)]
+        args: Vec<Value>,
+        /// Wallet of the scenario making the call, defaults to the master wallet.
+        from: Option<String>,
+        /// Base asset forwarded to the method, which must be payable.
+        #[serde(default)]
+        amount: u64,
+        #[serde(default)]
+        variable_outputs: usize,
+        /// Expected return value, unchecked if absent.
+        returns: Option<Value>,
+        /// Expected logs of the contract, in order. Unchecked if absent.
+        logs: Option<Vec<Value>>,
+        /// Expects the call to revert, with a reason containing this: what the contract logged
+        /// for a `require` or `revert_with_log`, or the reason of a panic. Any revert if empty.
+        reverts: Option<String>,
+        /// Measures the gas and fee of the call under this name, see [`Costs`].
+        cost: Option<String>,
+    },
+    Transfer {
+        /// A wallet of the scenario or an address.
+        to: String,
+        amount: u64,
+        asset: Option<AssetId>,
+        from: Option<String>,
+        cost: Option<String>,
+    },
+    Balance {
+        /// A wallet or a contract of the scenario, or an address.
+        of: String,
+        equals: u64,
+        asset: Option<AssetId>,
+    },
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Deploy { bin, .. } => write!(f, "deploy {bin}"),
+            Self::Call {
+                contract, method, ..
+            } => write!(f, "call {contract}.{method}"),
+            Self::Transfer { to, amount, .. } => write!(f, "transfer {amount} to {to}"),
+            Self::Balance { of, .. } => write!(f, "balance of {of}"),
+        }
+    }
+}
+
+/// What a passing scenario submitted.
+#[derive(Debug, Clone)]
+pub struct ScenarioReport {
+    pub name: String,
+    /// Outcome of every call and transfer, next to the step that submitted it. Calls expected to
+    /// revert and contracts already on chain have none.
+    pub transactions: Vec<(String, TxOutcome)>,
+}
+
+impl Scenario {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+
+        let mut scenario: Self = toml::from_str(&contents)
+            .wrap_err_with(|| format!("invalid scenario {}", path.display()))?;
+        if scenario.name.is_empty() {
+            scenario.name = file_stem(path)?;
+        }
+
+        Ok(scenario)
+    }
+
+    /// Every `*.toml` in `dir`, sorted by file name.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<Self>> {
+        let dir = dir.as_ref();
+        let mut paths = std::fs::read_dir(dir)
+            .wrap_err_with(|| format!("failed to read {}", dir.display()))?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "toml"));
+        paths.sort();
+
+        paths.iter().map(Self::load).collect()
+    }
+
+    /// Runs the steps in order, stopping at the first one that fails. Contracts are deployed as
+    /// the instance `scenario/<scenario>/<contract>`, see [`helpers::deploy`].
+    pub async fn run(&self, setup: &Setup) -> Result<ScenarioReport> {
+        let mut transactions = vec![];
+        self.run_collecting(setup, &mut transactions).await?;
+
+        Ok(ScenarioReport {
+            name: self.name.clone(),
+            transactions,
+        })
+    }
+
+    /// Like [`Scenario::run`], but adds the transactions to `transactions` as they are
+    /// submitted, so that those before a failing step are kept.
+    pub async fn run_collecting(
+        &self,
+        setup: &Setup,
+        transactions: &mut Vec<(String, TxOutcome)>,
+    ) -> Result<()> {
+        let mut run = Run {
+            setup,
+            scenario: self,
+            wallets: BTreeMap::new(),
+            contracts: HashMap::new(),
+            costs: Costs::named(&setup.baseline, &format!("scenario/{}", self.name)),
+            transactions,
+        };
+
+        let result = run.fund_and_step().await;
+        // on failure as well, the wallets would otherwise wait for the reclaim pass of a later run
+        let swept = run.sweep_wallets().await;
+        result?;
+        swept?;
+        run.costs.finish()
+    }
+}
+
+/// What the call that failed with `err` reverted with, according to the receipt that aborted it:
+/// the value the contract logged for a `require` or `revert_with_log`, with strings and unit enum
+/// variants unquoted, the reason of a panic, e.g. `NotEnoughBalance`, and otherwise what the SDK
+/// made of the revert code, e.g. `assertion failed`. Fails if the call didn't revert, e.g. if it
+/// was rejected before being submitted.
+fn revert_reason(err: &Error, abi: &Abi) -> Result<String> {
+    let Error::Transaction(Reason::Reverted {
+        reason, receipts, ..
+    }) = err
+    else {
+        bail!("the call failed without reverting: {err}");
+    };
+
+    let aborted = receipts
+        .iter()
+        .find(|receipt| matches!(receipt, Receipt::Revert { .. } | Receipt::Panic { .. }));
+    match aborted {
+        Some(Receipt::Panic { reason, .. }) => Ok(format!("{:?}", reason.reason())),
+        Some(Receipt::Revert { id, ra, .. })
+            if [FAILED_REQUIRE_SIGNAL, REVERT_WITH_LOG_SIGNAL].contains(ra) =>
+        {
+            let (_, log) = abi
+                .decode_logs(receipts, id)?
+                .pop()
+                .ok_or_else(|| eyre!("the contract reverted without logging why"))?;
+
+            Ok(match log {
+                Value::String(log) => log,
+                log => log.to_string(),
+            })
+        }
+        Some(_) => Ok(reason.clone()),
+        None => bail!("the call failed without a revert receipt: {err}"),
+    }
+}
+
+fn file_stem(path: &Path) -> Result<String> {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map(str::to_string)
+        .ok_or_else(|| eyre!("{} has no file name", path.display()))
+}
+
+struct Run<'a> {
+    setup: &'a Setup,
+    scenario: &'a Scenario,
+    wallets: BTreeMap<String, FreshWallet>,
+    contracts: HashMap<String, (Bech32ContractId, Abi)>,
+    costs: Costs,
+    transactions: &'a mut Vec<(String, TxOutcome)>,
+}
+
+impl Run<'_> {
+    async fn fund_and_step(&mut self) -> Result<()> {
+        let scenario = self.scenario;
+
+        for (name, amount) in &scenario.wallets {
+            let wallet = self
+                .setup
+                .fresh_wallet_named(&format!("scenario/{}/{name}", scenario.name), *amount)
+                .await?;
+            self.wallets.insert(name.clone(), wallet);
+        }
+
+        for (i, step) in scenario.steps.iter().enumerate() {
+            self.step(step).await.wrap_err_with(|| {
+                format!(
+                    "step {} of scenario '{}' failed: {step}",
+                    i + 1,
+                    scenario.name
+                )
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Sweeps every funded wallet, even if one of them fails. Returns the first error.
+    async fn sweep_wallets(&mut self) -> Result<()> {
+        let mut result = Ok(());
+
+        for wallet in std::mem::take(&mut self.wallets).into_values() {
+            let name = wallet.name().to_string();
+            if let Err(err) = wallet.sweep().await {
+                result = result.and(Err(err.wrap_err(format!("failed to sweep '{name}'"))));
+            }
+        }
+
+        result
+    }
+
+    async fn step(&mut self, step: &Step) -> Result<()> {
+        let outcome = match step {
+            Step::Deploy { bin, abi, name } => {
+                self.deploy(bin, abi.as_deref(), name.as_deref()).await?;
+                None
+            }
+            Step::Call {
+                contract,
+                method,
+                args,
+                from,
+                amount,
+                variable_outputs,
+                returns,
+                logs,
+                reverts,
+                ..
+            } => {
+                let (contract_id, abi) = self.contract(contract)?;
+                let function = abi.function(method)?;
+                let args = function.encode_args(args)?;
+
+                let mut handler: CallHandler<Wallet, ContractCall, ()> =
+                    CallHandler::new_contract_call(
+                        contract_id.clone(),
+                        self.account(from.as_deref())?.clone(),
+                        encode_fn_selector(method),
+                        &args,
+                        LogDecoder::default(),
+                        function.is_payable,
+                        EncoderConfig::default(),
+                    )
+                    .with_variable_output_policy(VariableOutputPolicy::Exactly(*variable_outputs));
+                if *amount > 0 {
+                    handler =
+                        handler.call_params(CallParameters::default().with_amount(*amount))?;
+                }
+
+                let response = match (handler.call().await, reverts) {
+                    (Ok(response), None) => response,
+                    (Ok(_), Some(_)) => bail!("expected the call to revert, it succeeded"),
+                    (Err(err), Some(expected)) => {
+                        let reason = revert_reason(&err, abi).wrap_err_with(|| {
+                            format!("expected the call to revert with '{expected}'")
+                        })?;
+                        ensure!(
+                            reason.contains(expected.as_str()),
+                            "expected the call to revert with '{expected}', it reverted with '{reason}'"
+                        );
+                        return Ok(());
+                    }
+                    (Err(err), None) => return Err(err.into()),
+                };
+
+                // receipts aren't part of the response under 0.71
+                let outcome = response.outcome(self.setup.wallet.try_provider()?).await?;
+                if let Some(expected) = returns {
+                    let token = ReceiptParser::new(&outcome.receipts, DecoderConfig::default())
+                        .parse_call(contract_id, &function.output)?;
+                    check(
+                        "return value",
+                        expected_json(&function.output, expected)?,
+                        json_from_token(&function.output, &token)?,
+                    )?;
+                }
+                if let Some(expected) = logs {
+                    let actual = abi.decode_logs(&outcome.receipts, &contract_id.into())?;
+                    // logs beyond those emitted are compared as given, and fail the check
+                    let expected = expected
+                        .iter()
+                        .enumerate()
+                        .map(|(i, expected)| match actual.get(i) {
+                            Some((param_type, _)) => expected_json(param_type, expected),
+                            None => Ok(expected.clone()),
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    check(
+                        "logs",
+                        Value::Array(expected),
+                        Value::Array(actual.into_iter().map(|(_, log)| log).collect()),
+                    )?;
+                }
+
+                Some(outcome)
+            }
+            Step::Transfer {
+                to,
+                amount,
+                asset,
+                from,
+                ..
+            } => {
+                let to = self.address(to)?;
+                let asset_id = self.asset_or_base(*asset).await?;
+
+                Some(
+                    self.account(from.as_deref())?
+                        .transfer_with_outcome(&to, *amount, asset_id)
+                        .await?,
+                )
+            }
+            Step::Balance { of, equals, asset } => {
+                let asset_id = self.asset_or_base(*asset).await?;
+                let provider = self.setup.wallet.try_provider()?;
+                let balance = match self.contracts.get(of) {
+                    Some((contract_id, _)) => {
+                        provider
+                            .get_contract_asset_balance(contract_id, asset_id)
+                            .await?
+                    }
+                    None => {
+                        provider
+                            .get_asset_balance(&self.address(of)?, asset_id)
+                            .await?
+                    }
+                };
+                check("balance", Value::from(*equals), Value::from(balance))?;
+
+                None
+            }
+        };
+
+        if let Some(outcome) = outcome {
+            if let Step::Call {
+                cost: Some(cost), ..
+            }
+            | Step::Transfer {
+                cost: Some(cost), ..
+            } = step
+            {
+                self.costs.record(cost, &outcome);
+            }
+            self.transactions.push((step.to_string(), outcome));
+        }
+
+        Ok(())
+    }
+
+    async fn deploy(&mut self, bin: &str, abi: Option<&Path>, name: Option<&str>) -> Result<()> {
+        let stem = file_stem(Path::new(bin))?;
+        let name = name.unwrap_or(&stem).to_string();
+        let abi = match abi {
+            Some(abi) => abi.to_path_buf(),
+            None => Path::new(bin).with_file_name(format!("{stem}-abi.json")),
+        };
+        let abi = Abi::load(abi)?;
+
+        let options = DeployOptions {
+            instance: Some(format!("scenario/{}/{name}", self.scenario.name)),
+            ..Default::default()
+        };
+        let contract_id = helpers::deploy(
+            &self.setup.wallet,
+            self.setup.deploy_config.clone(),
+            bin,
+            &options,
+        )
+        .await?
+        .contract_id;
+        self.contracts.insert(name, (contract_id, abi));
+
+        Ok(())
+    }
+
+    fn contract(&self, name: &str) -> Result<&(Bech32ContractId, Abi)> {
+        self.contracts.get(name).ok_or_else(|| {
+            eyre!("no contract named '{name}'")
+                .suggestion("deploy it in an earlier step, the name defaults to that of the binary")
+        })
+    }
+
+    /// The wallet of the scenario named `name`, the master wallet if `None`.
+    fn account(&self, name: Option<&str>) -> Result<&Wallet> {
+        let Some(name) = name else {
+            return Ok(&self.setup.wallet);
+        };
+
+        self.wallets
+            .get(name)
+            .map(FreshWallet::wallet)
+            .ok_or_else(|| {
+                eyre!("no wallet named '{name}'").suggestion("declare it under [wallets]")
+            })
+    }
+
+    /// A wallet of the scenario, or an address given as `fuel1...` or hex.
+    fn address(&self, target: &str) -> Result<Bech32Address> {
+        if let Some(wallet) = self.wallets.get(target) {
+            return Ok(wallet.wallet().address().clone());
+        }
+
+        Bech32Address::from_str(target)
+            .ok()
+            .or_else(|| Address::from_str(target).ok().map(Into::into))
+            .ok_or_else(|| {
+                eyre!("'{target}' is neither a wallet of the scenario nor an address")
+                    .suggestion("declare it under [wallets]")
+            })
+    }
+
+    async fn asset_or_base(&self, asset: Option<AssetId>) -> Result<AssetId> {
+        match asset {
+            Some(asset) => Ok(asset),
+            None => Ok(*self
+                .setup
+                .wallet
+                .try_provider()?
+                .consensus_parameters()
+                .await?
+                .base_asset_id()),
+        }
+    }
+}
+
+fn check(what: &str, expected: Value, actual: Value) -> Result<()> {
+    if expected != actual {
+        bail!("expected {what} {expected}, got {actual}");
+    }
+
+    Ok(())
+}
+
+/// `expected` as the contract would return it, so that e.g. `3` and `"3"` compare equal.
+fn expected_json(param_type: &ParamType, expected: &Value) -> Result<Value> {
+    let token = token_from_json(param_type, expected).wrap_err("invalid expected value")?;
+
+    json_from_token(param_type, &token)
+}
+
+/// The functions and logged types of a contract.
+#[derive(Debug, Clone)]
+pub struct Abi {
+    functions: HashMap<String, Function>,
+    logs: HashMap<u64, ParamType>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub name: String,
+    pub inputs: Vec<(String, ParamType)>,
+    pub output: ParamType,
+    pub is_payable: bool,
+}
+
+impl Abi {
+    /// Reads the JSON ABI forc writes to `out/<profile>/<name>-abi.json`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read {}", path.display()))
+            .suggestion("build the contract with forc, or point to the ABI with `abi`")?;
+
+        Self::from_json(&contents).wrap_err_with(|| format!("invalid ABI {}", path.display()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        let abi = UnifiedProgramABI::from_json_abi(json)?;
+        let types: HashMap<usize, UnifiedTypeDeclaration> = abi
+            .types
+            .iter()
+            .map(|declaration| (declaration.type_id, declaration.clone()))
+            .collect();
+
+        let functions = abi
+            .functions
+            .iter()
+            .map(|function| {
+                let inputs = function
+                    .inputs
+                    .iter()
+                    .map(|input| {
+                        Ok((
+                            input.name.clone(),
+                            ParamType::try_from_type_application(input, &types)?,
+                        ))
+                    })
+                    .collect::<Result<_>>()?;
+                let is_payable = function
+                    .attributes
+                    .iter()
+                    .flatten()
+                    .any(|attribute| attribute.name == "payable");
+
+                Ok((
+                    function.name.clone(),
+                    Function {
+                        name: function.name.clone(),
+                        inputs,
+                        output: ParamType::try_from_type_application(&function.output, &types)?,
+                        is_payable,
+                    },
+                ))
+            })
+            .collect::<Result<_>>()?;
+
+        let logs = abi
+            .logged_types
+            .iter()
+            .flatten()
+            .map(|logged| {
+                Ok((
+                    logged.log_id.parse()?,
+                    ParamType::try_from_type_application(&logged.application, &types)?,
+                ))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { functions, logs })
+    }
+
+    pub fn function(&self, name: &str) -> Result<&Function> {
+        self.functions.get(name).ok_or_else(|| {
+            let mut names = self.functions.keys().cloned().collect::<Vec<_>>();
+            names.sort();
+
+            eyre!(
+                "the ABI has no function '{name}', only {}",
+                names.join(", ")
+            )
+        })
+    }
+
+    /// Logs `contract_id` emitted in `receipts`, as JSON next to their type. Logs of types the
+    /// ABI doesn't know are left out.
+    pub fn decode_logs(
+        &self,
+        receipts: &[Receipt],
+        contract_id: &ContractId,
+    ) -> Result<Vec<(ParamType, Value)>> {
+        receipts
+            .iter()
+            .filter_map(|receipt| match receipt {
+                Receipt::LogData {
+                    id,
+                    rb,
+                    data: Some(data),
+                    ..
+                } if id == contract_id => Some((self.logs.get(rb)?, data)),
+                _ => None,
+            })
+            .map(|(param_type, data)| {
+                let token = ABIDecoder::default().decode(param_type, data.as_slice())?;
+
+                Ok((param_type.clone(), json_from_token(param_type, &token)?))
+            })
+            .collect()
+    }
+}
+
+impl Function {
+    /// `args` as tokens, one per input.
+    pub fn encode_args(&self, args: &[Value]) -> Result<Vec<Token>> {
+        if args.len() != self.inputs.len() {
+            let inputs = self
+                .inputs
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>();
+            bail!(
+                "{} takes {} arguments ({}), got {}",
+                self.name,
+                self.inputs.len(),
+                inputs.join(", "),
+                args.len()
+            );
+        }
+
+        self.inputs
+            .iter()
+            .zip(args)
+            .map(|((name, param_type), arg)| {
+                token_from_json(param_type, arg)
+                    .wrap_err_with(|| format!("invalid argument {name}"))
+            })
+            .collect()
+    }
+}
+
+/// Converts `value` to a token of `param_type`:
+/// - integers as numbers, or as decimal or `0x` hex strings for those too large for TOML
+/// - `b256`, `Bytes` and raw slices as `0x` hex strings
+/// - tuples, arrays and vectors as arrays, `()` as an empty array or `null`
+/// - structs as objects keyed by field name
+/// - enums as `{ "Variant" = value }`, or just `"Variant"` for variants without a value
+pub fn token_from_json(param_type: &ParamType, value: &Value) -> Result<Token> {
+    let mismatch = || eyre!("expected {}, got {value}", describe(param_type));
+
+    let token = match (param_type, value) {
+        (ParamType::Unit, Value::Null) => Token::Unit,
+        (ParamType::Unit, Value::Array(values)) if values.is_empty() => Token::Unit,
+        (ParamType::Bool, Value::Bool(value)) => Token::Bool(*value),
+        (ParamType::U8, _) => Token::U8(narrow(uint(value)?).ok_or_else(mismatch)?),
+        (ParamType::U16, _) => Token::U16(narrow(uint(value)?).ok_or_else(mismatch)?),
+        (ParamType::U32, _) => Token::U32(narrow(uint(value)?).ok_or_else(mismatch)?),
+        (ParamType::U64, _) => Token::U64(narrow(uint(value)?).ok_or_else(mismatch)?),
+        (ParamType::U128, _) => Token::U128(narrow(uint(value)?).ok_or_else(mismatch)?),
+        (ParamType::U256, _) => Token::U256(uint(value)?),
+        (ParamType::B256, Value::String(hex)) => {
+            Token::B256(*Bytes32::from_str(hex).map_err(|_| mismatch())?)
+        }
+        (ParamType::Bytes, Value::String(hex)) => Token::Bytes(bytes(hex).ok_or_else(mismatch)?),
+        (ParamType::RawSlice, Value::String(hex)) => {
+            Token::RawSlice(bytes(hex).ok_or_else(mismatch)?)
+        }
+        (ParamType::String, Value::String(value)) => Token::String(value.clone()),
+        (ParamType::StringArray(len), Value::String(value)) => {
+            Token::StringArray(StaticStringToken::new(value.clone(), Some(*len)))
+        }
+        (ParamType::StringSlice, Value::String(value)) => {
+            Token::StringSlice(StaticStringToken::new(value.clone(), None))
+        }
+        (ParamType::Tuple(param_types), Value::Array(values))
+            if param_types.len() == values.len() =>
+        {
+            Token::Tuple(tokens(param_types.iter(), values)?)
+        }
+        (ParamType::Array(param_type, len), Value::Array(values)) if *len == values.len() => {
+            Token::Array(tokens(std::iter::repeat(&**param_type), values)?)
+        }
+        (ParamType::Vector(param_type), Value::Array(values)) => {
+            Token::Vector(tokens(std::iter::repeat(&**param_type), values)?)
+        }
+        (ParamType::Struct { fields, .. }, Value::Object(values)) => {
+            if let Some(unknown) = values
+                .keys()
+                .find(|key| !fields.iter().any(|(name, _)| name == *key))
+            {
+                bail!("{} has no field {unknown}", describe(param_type));
+            }
+
+            let tokens = fields
+                .iter()
+                .map(|(name, param_type)| {
+                    let value = values
+                        .get(name)
+                        .ok_or_else(|| eyre!("missing field {name}"))?;
+
+                    token_from_json(param_type, value).wrap_err_with(|| format!("in field {name}"))
+                })
+                .collect::<Result<_>>()?;
+
+            Token::Struct(tokens)
+        }
+        (ParamType::Enum { enum_variants, .. }, _) => {
+            let (variant, value) = match value {
+                Value::String(variant) => (variant, &Value::Null),
+                Value::Object(values) if values.len() == 1 => {
+                    values.iter().next().expect("one entry")
+                }
+                _ => return Err(mismatch()),
+            };
+            let (discriminant, (_, variant_type)) = enum_variants
+                .variants()
+                .iter()
+                .enumerate()
+                .find(|(_, (name, _))| name == variant)
+                .ok_or_else(|| eyre!("{} has no variant {variant}", describe(param_type)))?;
+            let token = token_from_json(variant_type, value)
+                .wrap_err_with(|| format!("in variant {variant}"))?;
+
+            Token::Enum(Box::new((
+                discriminant as u64,
+                token,
+                enum_variants.clone(),
+            )))
+        }
+        _ => return Err(mismatch()),
+    };
+
+    Ok(token)
+}
+
+/// The inverse of [`token_from_json`]. Integers wider than 64 bits become decimal strings.
+pub fn json_from_token(param_type: &ParamType, token: &Token) -> Result<Value> {
+    let value = match (param_type, token) {
+        (_, Token::Unit) => Value::Null,
+        (_, Token::Bool(value)) => Value::from(*value),
+        (_, Token::U8(value)) => Value::from(*value),
+        (_, Token::U16(value)) => Value::from(*value),
+        (_, Token::U32(value)) => Value::from(*value),
+        (_, Token::U64(value)) => Value::from(*value),
+        (_, Token::U128(value)) => Value::from(value.to_string()),
+        (_, Token::U256(value)) => Value::from(value.to_string()),
+        (_, Token::B256(bytes)) => Value::from(format!("0x{}", hex_bytes::encode(bytes))),
+        (_, Token::Bytes(bytes) | Token::RawSlice(bytes)) => {
+            Value::from(format!("0x{}", hex_bytes::encode(bytes)))
+        }
+        (_, Token::String(value)) => Value::from(value.clone()),
+        (_, Token::StringArray(value) | Token::StringSlice(value)) => {
+            Value::from(value.get_encodable_str()?)
+        }
+        (ParamType::Tuple(param_types), Token::Tuple(tokens)) => {
+            Value::Array(values(param_types.iter(), tokens)?)
+        }
+        (
+            ParamType::Array(param_type, _) | ParamType::Vector(param_type),
+            Token::Array(tokens) | Token::Vector(tokens),
+        ) => Value::Array(values(std::iter::repeat(&**param_type), tokens)?),
+        (ParamType::Struct { fields, .. }, Token::Struct(tokens)) => Value::Object(
+            fields
+                .iter()
+                .zip(tokens)
+                .map(|((name, param_type), token)| {
+                    Ok((name.clone(), json_from_token(param_type, token)?))
+                })
+                .collect::<Result<_>>()?,
+        ),
+        (ParamType::Enum { .. }, Token::Enum(selector)) => {
+            let (discriminant, token, enum_variants) = &**selector;
+            let (name, variant_type) = enum_variants.select_variant(*discriminant)?;
+            match variant_type {
+                ParamType::Unit => Value::from(name.clone()),
+                _ => Value::Object(
+                    [(name.clone(), json_from_token(variant_type, token)?)]
+                        .into_iter()
+                        .collect(),
+                ),
+            }
+        }
+        _ => bail!("token {token} doesn't match {}", describe(param_type)),
+    };
+
+    Ok(value)
+}
+
+fn tokens<'a>(
+    param_types: impl Iterator<Item = &'a ParamType>,
+    values: &[Value],
+) -> Result<Vec<Token>> {
+    param_types
+        .zip(values)
+        .enumerate()
+        .map(|(i, (param_type, value))| {
+            token_from_json(param_type, value).wrap_err_with(|| format!("at index {i}"))
+        })
+        .collect()
+}
+
+fn values<'a>(
+    param_types: impl Iterator<Item = &'a ParamType>,
+    tokens: &[Token],
+) -> Result<Vec<Value>> {
+    param_types
+        .zip(tokens)
+        .map(|(param_type, token)| json_from_token(param_type, token))
+        .collect()
+}
+
+fn uint(value: &Value) -> Result<U256> {
+    let parsed = match value {
+        Value::Number(number) => number.as_u64().map(U256::from),
+        Value::String(string) => match string.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).ok(),
+            None => U256::from_dec_str(string).ok(),
+        },
+        _ => None,
+    };
+
+    parsed.ok_or_else(|| eyre!("expected an unsigned integer, got {value}"))
+}
+
+fn narrow<T: TryFrom<u128>>(value: U256) -> Option<T> {
+    u128::try_from(value).ok()?.try_into().ok()
+}
+
+fn bytes(hex: &str) -> Option<Vec<u8>> {
+    hex_bytes::decode(hex.strip_prefix("0x")?)
+}
+
+fn describe(param_type: &ParamType) -> String {
+    match param_type {
+        ParamType::Struct { name, .. } => format!("struct {name}"),
+        ParamType::Enum { name, .. } => format!("enum {name}"),
+        other => format!("{other:?}").to_lowercase(),
+    }
+}
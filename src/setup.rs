@@ -1,21 +1,302 @@
-use color_eyre::{eyre::Context, Result, Section};
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::PathBuf,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        LazyLock, OnceLock,
+    },
+};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result, Section,
+};
 use dotenv::dotenv;
 use fuels::{
-    accounts::provider::Provider, crypto::SecretKey, test_helpers::launch_provider_and_get_wallet,
+    accounts::provider::Provider,
+    core::constants::WORD_SIZE,
+    crypto::{PublicKey, SecretKey},
+    types::{
+        bech32::{Bech32Address, FUEL_BECH32_HRP},
+        ContractId,
+    },
+};
+
+use serde::Deserialize;
+
+use crate::{
+    baseline::{BaselineConfig, Costs},
+    cleanup::{self, ReclaimScope},
+    compat,
+    config::{Config, ConfigReport},
+    keys::KeySource,
+    local_node::LocalNodeConfig,
+    reservations::ReservingWallet,
+    retry,
+    wallets::{self, FreshWallet},
 };
 
-#[cfg(feature = "fuels_lts_70")]
-pub type Wallet = fuels::accounts::wallet::WalletUnlocked;
+pub use crate::compat::SdkWallet;
+
+/// Reserves the coins it spends so that concurrent tests can share it.
+pub type Wallet = ReservingWallet<SdkWallet>;
 
-#[cfg(feature = "fuels_71")]
-pub type Wallet = fuels::accounts::wallet::Wallet;
+/// Seed of the salts unless `DEPLOY_SEED` or `FORCE_DEPLOY` say otherwise.
+pub const DEFAULT_DEPLOY_SEED: &str = "e2e";
 
 #[derive(Debug, Clone)]
 pub struct DeployConfig {
     /// Whether to force deployment even if we already have an instance of the contract deployed.
+    /// Taken into account when resolving `seed`, which is random for forced runs even if
+    /// `DEPLOY_SEED` is set.
     pub force_deploy: bool,
-    /// Whether to deploy the contract in blobs (as a loader) or not.
-    pub deploy_in_blobs: bool,
+    /// Salts are derived from it along with the test name and the contract, so that every test
+    /// gets an instance of its own and reruns with the same seed get the same instances.
+    pub seed: String,
+    pub strategy: DeployStrategy,
+    /// Contracts already on chain to use instead of deploying the binaries of the same name.
+    pub existing: ExistingContracts,
+    /// Where deployments are recorded, `None` to keep no record.
+    pub registry: Option<PathBuf>,
+}
+
+/// Contract ids keyed by the name of the binary they were deployed from, without its `.bin`
+/// extension, e.g. `liquidity_pool`. `<name>=<contract id>,...`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "BTreeMap<String, String>")]
+pub struct ExistingContracts(BTreeMap<String, ContractId>);
+
+impl ExistingContracts {
+    /// The contract to use for the binary at `contract_bin`, if one is listed.
+    pub fn get(&self, contract_bin: &str) -> Option<ContractId> {
+        let name = std::path::Path::new(contract_bin).file_stem()?.to_str()?;
+
+        self.0.get(name).copied()
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, contract_id: ContractId) {
+        self.0.insert(name.into(), contract_id);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for ExistingContracts {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let contracts = s
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                entry
+                    .split_once('=')
+                    .map(|(name, id)| (name.trim().to_string(), id.trim().to_string()))
+                    .ok_or_else(|| {
+                        eyre!("invalid existing contract: {entry}")
+                            .suggestion("use '<binary name>=<contract id>', e.g. 'counter=0x12..'")
+                    })
+            })
+            .collect::<Result<BTreeMap<_, _>>>()?;
+
+        contracts.try_into()
+    }
+}
+
+impl TryFrom<BTreeMap<String, String>> for ExistingContracts {
+    type Error = color_eyre::Report;
+
+    fn try_from(contracts: BTreeMap<String, String>) -> Result<Self> {
+        contracts
+            .into_iter()
+            .map(|(name, id)| {
+                let contract_id = id
+                    .parse()
+                    .map_err(|e| eyre!("invalid contract id for {name}: {id} ({e})"))?;
+
+                Ok((name, contract_id))
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+}
+
+impl std::fmt::Display for ExistingContracts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (name, contract_id)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{name}={contract_id:#x}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How `helpers::deploy` puts a contract on chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum DeployStrategy {
+    /// The whole code in a single create transaction. `normal`
+    #[default]
+    Normal,
+    /// The code uploaded as blobs and a loader contract deployed on top. Blobs already on chain
+    /// aren't uploaded again. `loader`
+    Loader { blob_size: BlobStrategy },
+    /// Like `Loader`, but every deploy is a new loader contract referencing the same blobs, so
+    /// tests get fresh instances while the code is only uploaded once. `loader-reusing-blobs`
+    LoaderReusingBlobs { blob_size: BlobStrategy },
+}
+
+impl DeployStrategy {
+    /// Same strategy, loaders splitting the code according to `blob_size`.
+    pub fn with_blob_size(self, blob_size: BlobStrategy) -> Self {
+        match self {
+            Self::Loader { .. } => Self::Loader { blob_size },
+            Self::LoaderReusingBlobs { .. } => Self::LoaderReusingBlobs { blob_size },
+            Self::Normal => Self::Normal,
+        }
+    }
+}
+
+impl FromStr for DeployStrategy {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let blob_size = BlobStrategy::default();
+
+        let strategy = match s {
+            "normal" => Self::Normal,
+            "loader" => Self::Loader { blob_size },
+            "loader-reusing-blobs" => Self::LoaderReusingBlobs { blob_size },
+            _ if s.starts_with("existing:") => {
+                return Err(eyre!("invalid deploy strategy: {s}").suggestion(
+                    "list contracts already on chain per binary in EXISTING_CONTRACTS instead",
+                ))
+            }
+            _ => {
+                return Err(eyre!("invalid deploy strategy: {s}")
+                    .suggestion("use 'normal', 'loader' or 'loader-reusing-blobs'"))
+            }
+        };
+
+        Ok(strategy)
+    }
+}
+
+impl TryFrom<String> for DeployStrategy {
+    type Error = color_eyre::Report;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+/// The blob size is left out, it is reported as `BLOB_STRATEGY`.
+impl std::fmt::Display for DeployStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::Loader { .. } => write!(f, "loader"),
+            Self::LoaderReusingBlobs { .. } => write!(f, "loader-reusing-blobs"),
+        }
+    }
+}
+
+/// How to split a contract into blobs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum BlobStrategy {
+    /// At most this many blobs, all of the same size but the last one. `count:<n>`
+    Count(u64),
+    /// Blobs of this many bytes, a multiple of the word size. `size:<bytes>`
+    Size(u64),
+    /// As few blobs as `contract_max_size` allows. `max`
+    MaxAllowed,
+}
+
+impl Default for BlobStrategy {
+    fn default() -> Self {
+        Self::Count(3)
+    }
+}
+
+impl BlobStrategy {
+    /// Words per blob for a contract of `contract_size` bytes, never more than
+    /// `max_contract_size` bytes fit in.
+    pub fn words_per_blob(&self, contract_size: u64, max_contract_size: u64) -> Result<usize> {
+        let word_size = WORD_SIZE as u64;
+        let max_words = max_contract_size / word_size;
+        let contract_words = contract_size.div_ceil(word_size);
+
+        let words = match *self {
+            Self::Count(count) => contract_words.div_ceil(count),
+            Self::Size(bytes) => bytes / word_size,
+            Self::MaxAllowed => max_words,
+        };
+
+        if words > max_words {
+            return Err(eyre!(
+                "blobs of {words} words exceed the max contract size of {max_contract_size} bytes"
+            )
+            .suggestion("use fewer bytes per blob or more blobs, or set BLOB_STRATEGY=max"));
+        }
+
+        Ok(words.max(1) as usize)
+    }
+}
+
+impl FromStr for BlobStrategy {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            eyre!("invalid blob strategy: {s}")
+                .suggestion("use 'count:<blobs>', 'size:<bytes>' or 'max'")
+        };
+        let number = |value: &str| value.parse::<u64>().ok().filter(|n| *n > 0);
+
+        let strategy = match s.split_once(':') {
+            None if s == "max" => Self::MaxAllowed,
+            Some(("count", count)) => Self::Count(number(count).ok_or_else(invalid)?),
+            Some(("size", bytes)) => {
+                let bytes = number(bytes).ok_or_else(invalid)?;
+                if bytes % WORD_SIZE as u64 != 0 {
+                    return Err(
+                        eyre!("blob size of {bytes} bytes is not a whole number of words")
+                            .suggestion(format!("use a multiple of {WORD_SIZE}")),
+                    );
+                }
+                Self::Size(bytes)
+            }
+            _ => return Err(invalid()),
+        };
+
+        Ok(strategy)
+    }
+}
+
+impl TryFrom<String> for BlobStrategy {
+    type Error = color_eyre::Report;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl std::fmt::Display for BlobStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Count(count) => write!(f, "count:{count}"),
+            Self::Size(bytes) => write!(f, "size:{bytes}"),
+            Self::MaxAllowed => write!(f, "max"),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -24,97 +305,238 @@ pub struct Setup {
     pub wallet: Wallet,
     /// Tweaking how contracts should be deployed
     pub deploy_config: DeployConfig,
+    /// Whether the costs measured by the tests are recorded or compared
+    pub baseline: BaselineConfig,
+    /// Where the reports of a run are written, `None` to write none
+    pub report_dir: Option<PathBuf>,
+    /// Every resolved setting and where it came from
+    pub report: ConfigReport,
+    /// Private key of `wallet`, used to derive the fresh wallets
+    pub(crate) master_key: MasterKey,
+}
+
+impl Setup {
+    /// A wallet dedicated to the calling test, funded with `amount` of the base asset from
+    /// `wallet`. See [`FreshWallet`].
+    pub async fn fresh_wallet(&self, amount: u64) -> Result<FreshWallet> {
+        let name = wallets::current_test_name()?;
+
+        self.fresh_wallet_named(&name, amount).await
+    }
+
+    /// Same as [`Setup::fresh_wallet`] but for callers not running inside a `#[test]`.
+    pub async fn fresh_wallet_named(&self, name: &str, amount: u64) -> Result<FreshWallet> {
+        FreshWallet::fund(&self.wallet, &self.master_key.0, name, amount).await
+    }
+
+    /// Collects the gas and fee of the calling test's transactions, see [`Costs`].
+    pub fn costs(&self) -> Result<Costs> {
+        Costs::new(&self.baseline)
+    }
+}
+
+/// Kept out of `Debug` so that it doesn't end up in test output.
+#[derive(Clone)]
+pub(crate) struct MasterKey(pub(crate) SecretKey);
+
+impl std::fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MasterKey(<redacted>)")
+    }
+}
+
+pub fn wallet_from_key(key: SecretKey, provider: Provider) -> Wallet {
+    ReservingWallet::new(compat::wallet_from_key(key, provider))
+}
+
+pub(crate) fn address_of(key: &SecretKey) -> Bech32Address {
+    Bech32Address::new(FUEL_BECH32_HRP, PublicKey::from(key).hash())
 }
 
 pub async fn init() -> Result<Setup> {
     // affects global state so it can fail if already set
     let _ = color_eyre::install();
 
-    // It can fail if there is no file, that's ok.
-    let _ = dotenv();
+    init_with(Config::load()?).await
+}
 
-    let chain = read_chain()?;
+/// Same as [`init`] but with a config resolved by the caller, e.g. from the flags of `fuel-e2e`.
+pub async fn init_with(config: Config) -> Result<Setup> {
+    let _ = color_eyre::install();
 
-    let wallet = chain.wallet().await?;
+    let setup = if config.shares_setup() {
+        shared_setup(config).await?
+    } else {
+        config.setup().await?
+    };
+    eprintln!("{}", setup.report);
+    reclaim_abandoned(&setup).await?;
 
-    let force_deploy = check_boolean_env("FORCE_DEPLOY")?;
-    let deploy_in_blobs = check_boolean_env("DEPLOY_IN_BLOBS")?;
+    Ok(setup)
+}
 
-    Ok(Setup {
-        wallet,
-        deploy_config: DeployConfig {
-            force_deploy,
-            deploy_in_blobs,
-        },
-    })
+/// Same as [`init`], but the setup (provider, funded wallet and local node, if any) is created
+/// once per process and every caller gets a handle to it, regardless of
+/// [`Config::shares_setup`].
+pub async fn shared() -> Result<Setup> {
+    let _ = color_eyre::install();
+
+    let setup = shared_setup(Config::load()?).await?;
+    eprintln!("{}", setup.report);
+    reclaim_abandoned(&setup).await?;
+
+    Ok(setup)
 }
 
-fn check_boolean_env(env: &str) -> Result<bool> {
-    let Some(env) = read_env(env).ok() else {
-        return Ok(false);
+static RECLAIMED: AtomicBool = AtomicBool::new(false);
+
+/// Sweeps what killed runs left behind, see [`cleanup`]. Done by the first caller of the
+/// process only, the others would race it for the same coins.
+async fn reclaim_abandoned(setup: &Setup) -> Result<()> {
+    if RECLAIMED.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    let report = cleanup::reclaim(setup, ReclaimScope::Abandoned).await?;
+    if !report.reclaimed.is_empty() || !report.kept.is_empty() {
+        eprintln!("{report}");
+    }
+
+    Ok(())
+}
+
+/// Name of the worker threads of [`SHARED_RUNTIME`].
+pub(crate) const SHARED_RUNTIME_THREAD: &str = "e2e-shared-setup";
+
+/// Every `#[tokio::test]` runs on a runtime of its own that is dropped when the test ends, along
+/// with the tasks spawned on it. The shared setup is created on this one instead so that the
+/// local node and the provider's connections outlive the test that happened to go first.
+static SHARED_RUNTIME: LazyLock<tokio::runtime::Runtime> = LazyLock::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .thread_name(SHARED_RUNTIME_THREAD)
+        .enable_all()
+        .build()
+        .expect("failed to start the runtime of the shared setup")
+});
+
+static SHARED_SETUP: tokio::sync::OnceCell<Setup> = tokio::sync::OnceCell::const_new();
+
+async fn shared_setup(config: Config) -> Result<Setup> {
+    let setup = SHARED_SETUP
+        .get_or_try_init(|| async move {
+            SHARED_RUNTIME
+                .spawn(async move { config.setup().await })
+                .await?
+        })
+        .await?;
+
+    Ok(setup.clone())
+}
+
+static DOTENV_VARS: OnceLock<HashSet<String>> = OnceLock::new();
+
+/// Loads the `.env` file once per process. Variables already present in the environment are not
+/// overridden. Returns the names of the variables that were taken from the file.
+pub(crate) fn load_dotenv() -> &'static HashSet<String> {
+    let env_var_names = || {
+        std::env::vars_os()
+            .filter_map(|(name, _)| name.into_string().ok())
+            .collect::<HashSet<_>>()
     };
 
-    Ok(env.to_lowercase() == "true")
+    DOTENV_VARS.get_or_init(|| {
+        let already_set = env_var_names();
+
+        // It can fail if there is no file, that's ok.
+        let _ = dotenv();
+
+        env_var_names()
+            .into_iter()
+            .filter(|name| !already_set.contains(name))
+            .collect()
+    })
+}
+
+/// Only `true` and `false` (case insensitive) are accepted, anything else is most likely a typo.
+pub(crate) fn parse_boolean_env(env: &str, value: &str) -> Result<bool> {
+    match value.to_lowercase().as_str() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(eyre!("invalid value for {env}: {value:?}")
+            .suggestion(format!("set {env} to either 'true' or 'false'"))),
+    }
 }
 
-enum Chain {
-    Local,
+pub(crate) enum Chain {
+    Local(LocalNodeConfig),
     Devnet,
     Testnet,
+    /// Any fuel-core endpoint, e.g. a private staging node.
+    Custom {
+        url: String,
+        /// Name of the env variable holding the private key.
+        key_env: &'static str,
+    },
 }
 
 impl Chain {
-    async fn wallet(&self) -> Result<Wallet> {
-        let wallet_from_env_key = |env_var: &'static str, url: &'static str| async move {
-            let provider = Provider::connect(url)
-                .await
-                .wrap_err_with(|| format!("failed to connect to {url}"))?;
-
-            let key: SecretKey = read_env(env_var)?
-                .parse()
-                .wrap_err("given private key is invalid")?;
-
-            #[cfg(feature = "fuels_lts_70")]
-            let wallet = Wallet::new_from_private_key(key, Some(provider));
-            #[cfg(feature = "fuels_71")]
-            let wallet = {
-                let signer = fuels::accounts::signers::private_key::PrivateKeySigner::new(key);
-                Wallet::new(signer, provider)
-            };
-
-            Result::<_>::Ok(wallet)
-        };
+    /// `None` for the local node since its address is only known once it is spawned.
+    pub(crate) fn url(&self) -> Option<&str> {
+        match self {
+            Chain::Local(_) => None,
+            Chain::Devnet => Some("https://devnet.fuel.network"),
+            Chain::Testnet => Some("https://testnet.fuel.network"),
+            Chain::Custom { url, .. } => Some(url),
+        }
+    }
 
-        let wallet = match self {
-            Chain::Devnet => wallet_from_env_key("DEV_KEY", "https://devnet.fuel.network").await?,
-            Chain::Testnet => {
-                wallet_from_env_key("TESTNET_KEY", "https://testnet.fuel.network").await?
-            }
-            Chain::Local => launch_provider_and_get_wallet().await?,
-        };
+    /// Name of the env variable holding the private key of the funded wallet. See [`KeySource`]
+    /// for the alternatives derived from it.
+    pub(crate) fn key_env(&self) -> Option<&'static str> {
+        match self {
+            Chain::Local(_) => None,
+            Chain::Devnet => Some("DEV_KEY"),
+            Chain::Testnet => Some("TESTNET_KEY"),
+            Chain::Custom { key_env, .. } => Some(key_env),
+        }
+    }
+
+    /// The funded wallet along with its private key.
+    pub(crate) async fn wallet(&self) -> Result<(Wallet, SecretKey)> {
+        if let Chain::Local(node) = self {
+            return node.launch().await;
+        }
+
+        let url = self.url().expect("only the local chain has no url");
+        let key_env = self.key_env().expect("only the local chain has no key");
+
+        let provider = retry::policy()
+            .query(&format!("connecting to {url}"), || async {
+                Ok(Provider::connect(url).await?)
+            })
+            .await
+            .wrap_err_with(|| format!("failed to connect to {url}"))?;
+
+        let key = KeySource::from_env(key_env)?.secret_key()?;
+
+        Ok((wallet_from_key(key, provider.clone()), key))
+    }
+}
 
-        Ok(wallet)
+impl std::fmt::Display for Chain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Chain::Local(_) => write!(f, "local"),
+            Chain::Devnet => write!(f, "devnet"),
+            Chain::Testnet => write!(f, "testnet"),
+            Chain::Custom { .. } => write!(f, "custom"),
+        }
     }
 }
 
-fn read_env(name: &str) -> Result<String> {
+pub(crate) fn read_env(name: &str) -> Result<String> {
     let msg =
         format!("did you setup {name} env variable? add them in a .env file e.g. {name}=abcd...");
     std::env::var(name).suggestion(msg)
 }
-
-fn read_chain() -> Result<Chain> {
-    let var = read_env("TARGET_CHAIN")?;
-
-    let env = match var.as_str() {
-        "devnet" => Chain::Devnet,
-        "testnet" => Chain::Testnet,
-        "local" => Chain::Local,
-        env => {
-            return Err(color_eyre::eyre::eyre!("invalid target chain value: {env}")
-                .suggestion("use 'devnet' or 'testnet'"))
-        }
-    };
-
-    Ok(env)
-}
@@ -0,0 +1,195 @@
+mod liquidity_pool;
+mod multi_call;
+mod pay_contract_call_with_predicate;
+mod predicate_blobs;
+mod predicates;
+mod transfers;
+
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use color_eyre::{eyre::eyre, Result, Section};
+
+use crate::{
+    outcome::TxOutcome,
+    report,
+    scenario::Scenario,
+    setup::{self, Setup},
+};
+
+/// A check the `fuel-e2e` binary can run against a chain without a Rust toolchain: one of the
+/// checks built into the harness, or a scenario file.
+#[derive(Debug, Clone)]
+pub struct Suite {
+    pub name: String,
+    pub description: String,
+    kind: SuiteKind,
+}
+
+#[derive(Debug, Clone)]
+enum SuiteKind {
+    Builtin(Builtin),
+    Scenario(Box<Scenario>),
+}
+
+/// The checks built into the harness. Each also runs as the `cargo test` of the same name, see
+/// [`run_test`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Builtin {
+    Transfers,
+    Predicates,
+    LiquidityPool,
+    MultiCall,
+    PredicateBlobs,
+    PayContractCallWithPredicate,
+}
+
+impl Builtin {
+    pub const ALL: [Self; 6] = [
+        Self::Transfers,
+        Self::Predicates,
+        Self::LiquidityPool,
+        Self::MultiCall,
+        Self::PredicateBlobs,
+        Self::PayContractCallWithPredicate,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Transfers => "transfers",
+            Self::Predicates => "predicates",
+            Self::LiquidityPool => "liquidity_pool",
+            Self::MultiCall => "multi_call",
+            Self::PredicateBlobs => "predicate_blobs",
+            Self::PayContractCallWithPredicate => "pay_contract_call_with_predicate",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Transfers => "funds and sweeps a fresh wallet, checking the fee charged",
+            Self::Predicates => "funds, spends from and sweeps the open predicate",
+            Self::LiquidityPool => "deposits into and withdraws from the liquidity pool contract",
+            Self::MultiCall => "calls the counter contract four times in one transaction",
+            Self::PredicateBlobs => "funds, spends from and sweeps a predicate loaded from a blob",
+            Self::PayContractCallWithPredicate => {
+                "calls the counter contract with the fee paid by a predicate"
+            }
+        }
+    }
+
+    /// Adds the transactions the suite submits to `transactions` as it goes, named after the
+    /// step that did, so that those before a failing step are kept.
+    pub async fn run(
+        &self,
+        setup: &Setup,
+        transactions: &mut Vec<(String, TxOutcome)>,
+    ) -> Result<()> {
+        match self {
+            Self::Transfers => transfers::run(setup, transactions).await,
+            Self::Predicates => predicates::run(setup, transactions).await,
+            Self::LiquidityPool => liquidity_pool::run(setup, transactions).await,
+            Self::MultiCall => multi_call::run(setup, transactions).await,
+            Self::PredicateBlobs => predicate_blobs::run(setup, transactions).await,
+            Self::PayContractCallWithPredicate => {
+                pay_contract_call_with_predicate::run(setup, transactions).await
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for Builtin {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|builtin| builtin.name() == s)
+            .ok_or_else(|| {
+                eyre!("unknown built-in suite {s}").suggestion("list them with `fuel-e2e list`")
+            })
+    }
+}
+
+/// Runs the built-in suite `name` against the chain `setup::init` resolves and adds it to the
+/// report of the process, see [`report::record_test`]. The body of the `cargo test` of the same
+/// name.
+pub async fn run_test(name: &str) -> Result<()> {
+    let builtin: Builtin = name.parse()?;
+    let setup = setup::init().await?;
+
+    let run = Suite::from(builtin).run(&setup).await;
+    report::record_test(&setup, &run).await?;
+
+    run.result
+}
+
+impl From<Builtin> for Suite {
+    fn from(builtin: Builtin) -> Self {
+        Self {
+            name: builtin.name().to_string(),
+            description: builtin.description().to_string(),
+            kind: SuiteKind::Builtin(builtin),
+        }
+    }
+}
+
+/// The built-in suites followed by one `scenario:<name>` suite per file in `scenario_dir`. A
+/// missing directory has no scenarios.
+pub fn all(scenario_dir: impl AsRef<Path>) -> Result<Vec<Suite>> {
+    let mut suites = Builtin::ALL.map(Suite::from).to_vec();
+
+    let scenario_dir = scenario_dir.as_ref();
+    if scenario_dir.exists() {
+        suites.extend(
+            Scenario::load_dir(scenario_dir)?
+                .into_iter()
+                .map(|scenario| Suite {
+                    name: format!("scenario:{}", scenario.name),
+                    description: scenario.description.clone(),
+                    kind: SuiteKind::Scenario(Box::new(scenario)),
+                }),
+        );
+    }
+
+    Ok(suites)
+}
+
+/// What became of a suite.
+#[derive(Debug)]
+pub struct SuiteRun {
+    pub name: String,
+    pub duration: Duration,
+    /// The transactions the suite submitted, named after the step that did. Those submitted
+    /// before a failure too.
+    pub transactions: Vec<(String, TxOutcome)>,
+    pub result: Result<()>,
+}
+
+impl SuiteRun {
+    pub fn passed(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+impl Suite {
+    pub async fn run(&self, setup: &Setup) -> SuiteRun {
+        let start = Instant::now();
+        let mut transactions = vec![];
+        let result = match &self.kind {
+            SuiteKind::Builtin(builtin) => builtin.run(setup, &mut transactions).await,
+            SuiteKind::Scenario(scenario) => {
+                scenario.run_collecting(setup, &mut transactions).await
+            }
+        };
+
+        SuiteRun {
+            name: self.name.clone(),
+            duration: start.elapsed(),
+            transactions,
+            result,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use color_eyre::{eyre::ensure, Result};
+use fuels::accounts::ViewOnlyAccount;
+
+use crate::{
+    baseline::Costs,
+    fees::ExpectedFee,
+    helpers::DeployOptions,
+    liquidity_pool::{DepositCompleted, DepositEvent, Fixture},
+    outcome::TxOutcome,
+    setup::Setup,
+};
+
+/// Deposits into the liquidity pool and withdraws again, checking the minted amount, the event
+/// and the balances.
+pub async fn run(setup: &Setup, transactions: &mut Vec<(String, TxOutcome)>) -> Result<()> {
+    // a wallet of our own so that the balance checks aren't affected by other spenders of the
+    // master wallet, e.g. tests running in parallel
+    let wallet = setup.fresh_wallet_named("liquidity_pool", 100_000).await?;
+    let provider = wallet.try_provider()?;
+    let mut costs = Costs::named(&setup.baseline, "liquidity_pool");
+
+    let fixture = Fixture::deploy(
+        &setup.wallet,
+        setup.deploy_config.clone(),
+        &DeployOptions::default(),
+    )
+    .await?
+    .connect(wallet.wallet().clone());
+
+    // so that we don't lose funds in cases when the test failed/was killed before we reclaimed the deposit
+    fixture.reclaim_any_previous_deposits().await?;
+
+    let deposit_amount = 100;
+
+    let pre_deposit_balances = fixture.current_balances().await?;
+    let pre_deposit_total = fixture.total_deposited_ever().await?;
+
+    let DepositCompleted {
+        outcome: deposited,
+        event,
+    } = fixture.deposit(deposit_amount).await?;
+    transactions.push(("deposit".to_string(), deposited.clone()));
+    let logged = deposited.decode_logs_with_type::<DepositEvent>()?;
+    ensure!(
+        logged == vec![event.clone()],
+        "expected the deposit to log {event:?}, got {logged:?}"
+    );
+    // the balances below only hold if the node charged what the fee schedule says
+    ExpectedFee::assert_matches(provider, &deposited, 0).await?;
+    costs.record("deposit", &deposited);
+
+    let post_deposit_total = fixture.total_deposited_ever().await?;
+    let post_deposit_balances = fixture.current_balances().await?;
+
+    // contract configured to mint 2x the amount deposited
+    let amount_minted = deposit_amount * 2;
+
+    let expected = DepositEvent {
+        amount: deposit_amount,
+        minted: amount_minted,
+        to: wallet.address().into(),
+    };
+    ensure!(
+        event == expected,
+        "expected the deposit event {expected:?}, got {event:?}"
+    );
+
+    ensure!(
+        post_deposit_total == pre_deposit_total + deposit_amount,
+        "expected {} deposited in total, got {post_deposit_total}",
+        pre_deposit_total + deposit_amount
+    );
+
+    let expected_base = pre_deposit_balances.base - deposit_amount - deposited.total_fee;
+    ensure!(
+        post_deposit_balances.base == expected_base,
+        "expected a base balance of {expected_base} after the deposit, got {}",
+        post_deposit_balances.base
+    );
+
+    let expected_minted = pre_deposit_balances.minted + amount_minted;
+    ensure!(
+        post_deposit_balances.minted == expected_minted,
+        "expected a minted balance of {expected_minted} after the deposit, got {}",
+        post_deposit_balances.minted
+    );
+
+    let pre_withdraw_balances = fixture.current_balances().await?;
+    let withdrawn = fixture.withdraw(amount_minted).await?;
+    transactions.push(("withdraw".to_string(), withdrawn.clone()));
+    ensure!(
+        withdrawn.block_height > 0,
+        "the withdrawal has no block height"
+    );
+    ExpectedFee::assert_matches(provider, &withdrawn, 0).await?;
+    costs.record("withdraw", &withdrawn);
+    let post_withdraw_balances = fixture.current_balances().await?;
+
+    let expected_base = pre_withdraw_balances.base + deposit_amount - withdrawn.total_fee;
+    ensure!(
+        post_withdraw_balances.base == expected_base,
+        "expected a base balance of {expected_base} after the withdrawal, got {}",
+        post_withdraw_balances.base
+    );
+
+    wallet.sweep().await?;
+    costs.finish()
+}
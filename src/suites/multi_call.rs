@@ -0,0 +1,50 @@
+use color_eyre::{eyre::ensure, Result};
+use fuels::{accounts::ViewOnlyAccount, macros::abigen, programs::calls::CallHandler};
+
+use crate::{
+    baseline::Costs,
+    helpers::{self, DeployOptions},
+    outcome::{CallResponseExt, TxOutcome},
+    setup::Setup,
+};
+
+abigen!(Contract(
+    name = "MyContract",
+    abi = "sway/contract_test/out/release/contract_test-abi.json"
+));
+
+/// Calls the counter contract four times in a single transaction.
+pub async fn run(setup: &Setup, transactions: &mut Vec<(String, TxOutcome)>) -> Result<()> {
+    let wallet = &setup.wallet;
+
+    let contract_id = helpers::deploy(
+        wallet,
+        setup.deploy_config.clone(),
+        "sway/contract_test/out/release/contract_test.bin",
+        &DeployOptions::default(),
+    )
+    .await?
+    .contract_id;
+
+    let contract_methods = MyContract::new(contract_id, wallet.clone()).methods();
+
+    let multi_call_handler = CallHandler::new_multi_call(wallet.clone())
+        .add_call(contract_methods.initialize_counter(0))
+        .add_call(contract_methods.increment_counter(3))
+        .add_call(contract_methods.increment_counter(6))
+        .add_call(contract_methods.increment_counter(9));
+
+    let response = multi_call_handler.call::<(u64, u64, u64, u64)>().await?;
+
+    ensure!(
+        response.value == (0, 3, 9, 18),
+        "expected the calls to return (0, 3, 9, 18), got {:?}",
+        response.value
+    );
+
+    let outcome = response.outcome(wallet.try_provider()?).await?;
+    transactions.push(("multi_call".to_string(), outcome.clone()));
+    let mut costs = Costs::named(&setup.baseline, "multi_call");
+    costs.record("multi_call", &outcome);
+    costs.finish()
+}
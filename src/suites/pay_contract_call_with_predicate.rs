@@ -0,0 +1,107 @@
+use color_eyre::{
+    eyre::{ensure, eyre},
+    Result,
+};
+use fuels::{accounts::ViewOnlyAccount, macros::abigen};
+
+use crate::{
+    helpers::{self, DeployOptions},
+    outcome::{CallResponseExt, TxOutcome},
+    predicates::{PredicateFixture, PredicateOptions},
+    setup::Setup,
+};
+
+abigen!(
+    Contract(
+        name = "MyContract",
+        abi = "sway/contract_test/out/release/contract_test-abi.json"
+    ),
+    Predicate(
+        name = "MyPredicate",
+        abi = "sway/predicate_blobs/out/release/predicate_blobs-abi.json"
+    )
+);
+
+/// Calls the counter contract with the fee paid by a predicate.
+pub async fn run(setup: &Setup, transactions: &mut Vec<(String, TxOutcome)>) -> Result<()> {
+    // a wallet of our own so that the balance checks aren't affected by tests running in parallel
+    let wallet = setup
+        .fresh_wallet_named("pay_contract_call_with_predicate", 500_000)
+        .await?;
+    let provider = wallet.try_provider()?;
+
+    let predicate = PredicateFixture::load(
+        "sway/predicate_blobs/out/release/predicate_blobs.bin",
+        &wallet,
+        PredicateOptions {
+            data: MyPredicateEncoder::default().encode_data(1, 19)?,
+            configurables: MyPredicateConfigurables::default()
+                .with_SECRET_NUMBER(10001)?
+                .into(),
+            ..PredicateOptions::default()
+        },
+    )
+    .await?;
+
+    // empty out predicate if it has any coins left
+    predicate.sweep().await?;
+
+    let amount = 250_000;
+    transactions.push(("fund".to_string(), predicate.fund(amount).await?));
+    let balance = predicate.balance().await?;
+    ensure!(
+        balance == amount,
+        "expected the predicate to hold {amount}, got {balance}"
+    );
+
+    let contract_id = helpers::deploy(
+        &setup.wallet,
+        setup.deploy_config.clone(),
+        "sway/contract_test/out/release/contract_test.bin",
+        &DeployOptions::default(),
+    )
+    .await?
+    .contract_id;
+
+    // call contract method with predicate
+    let response = MyContract::new(contract_id, predicate.predicate().clone())
+        .methods()
+        .initialize_counter(42)
+        .call()
+        .await?;
+    transactions.push((
+        "initialize_counter".to_string(),
+        response.outcome(provider).await?,
+    ));
+    ensure!(
+        response.value == 42,
+        "expected the call to return 42, got {}",
+        response.value
+    );
+
+    // transfer all coins from predicate back to wallet
+    let base_asset_id = *provider.consensus_parameters().await?.base_asset_id();
+    let wallet_amount_before_return = wallet.get_asset_balance(&base_asset_id).await?;
+    let left_in_predicate = predicate.balance().await?;
+    let swept = predicate
+        .sweep()
+        .await?
+        .ok_or_else(|| eyre!("the predicate had nothing to sweep"))?;
+    transactions.push(("sweep".to_string(), swept.clone()));
+    let balance = predicate.balance().await?;
+    ensure!(
+        balance == 0,
+        "the predicate still holds {balance} after the sweep"
+    );
+
+    let wallet_amount_after_return = wallet.get_asset_balance(&base_asset_id).await?;
+    let expected = wallet_amount_before_return + left_in_predicate - swept.total_fee;
+    ensure!(
+        wallet_amount_after_return == expected,
+        "expected the wallet to hold {expected} after the sweep, got {wallet_amount_after_return}"
+    );
+
+    wallet.sweep().await?;
+
+    Ok(())
+}
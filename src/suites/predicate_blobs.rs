@@ -0,0 +1,73 @@
+use color_eyre::{
+    eyre::{ensure, eyre},
+    Result,
+};
+use fuels::{accounts::ViewOnlyAccount, macros::abigen};
+
+use crate::{
+    outcome::TxOutcome,
+    predicates::{PredicateFixture, PredicateKind, PredicateOptions},
+    setup::Setup,
+};
+
+abigen!(Predicate(
+    name = "MyPredicate",
+    abi = "sway/predicate_blobs/out/release/predicate_blobs-abi.json"
+));
+
+/// Funds, spends from and sweeps a predicate loaded from a blob.
+pub async fn run(setup: &Setup, transactions: &mut Vec<(String, TxOutcome)>) -> Result<()> {
+    // a wallet of our own so that the balance checks aren't affected by tests running in parallel
+    let wallet = setup.fresh_wallet_named("predicate_blobs", 100_000).await?;
+
+    let predicate = PredicateFixture::load(
+        "sway/predicate_blobs/out/release/predicate_blobs.bin",
+        &wallet,
+        PredicateOptions {
+            kind: PredicateKind::Loader,
+            data: MyPredicateEncoder::default().encode_data(1, 19)?,
+            configurables: MyPredicateConfigurables::default()
+                .with_SECRET_NUMBER(10001)?
+                .into(),
+        },
+    )
+    .await?;
+
+    // empty out predicate if it has any coins left
+    predicate.sweep().await?;
+
+    let amount = 10_000;
+    transactions.push(("fund".to_string(), predicate.fund(amount).await?));
+    let balance = predicate.balance().await?;
+    ensure!(
+        balance == amount,
+        "expected the predicate to hold {amount}, got {balance}"
+    );
+
+    // spending through the loader only works if the blob it points to was uploaded
+    let spent = predicate.spend(wallet.address(), 1_000).await?;
+    transactions.push(("spend".to_string(), spent.clone()));
+    ensure!(spent.is_success(), "spending from the predicate failed");
+    let balance = predicate.balance().await?;
+    let expected = amount - 1_000 - spent.total_fee;
+    ensure!(
+        balance == expected,
+        "expected the predicate to hold {expected} after spending, got {balance}"
+    );
+
+    let swept = predicate
+        .sweep()
+        .await?
+        .ok_or_else(|| eyre!("the predicate had nothing to sweep"))?;
+    transactions.push(("sweep".to_string(), swept.clone()));
+    ensure!(swept.total_fee > 0, "the sweep was charged no fee");
+    let balance = predicate.balance().await?;
+    ensure!(
+        balance == 0,
+        "the predicate still holds {balance} after the sweep"
+    );
+
+    wallet.sweep().await?;
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use color_eyre::{eyre::eyre, Result};
+use fuels::accounts::ViewOnlyAccount;
+
+use crate::{
+    outcome::TxOutcome,
+    predicates::{PredicateFixture, PredicateOptions, OPEN_PREDICATE_BIN},
+    setup::Setup,
+};
+
+/// Funds, spends from and sweeps the open predicate.
+pub async fn run(setup: &Setup, transactions: &mut Vec<(String, TxOutcome)>) -> Result<()> {
+    let predicate = PredicateFixture::load(
+        OPEN_PREDICATE_BIN,
+        &setup.wallet,
+        PredicateOptions::open(3)?,
+    )
+    .await?;
+
+    predicate.sweep().await?;
+    transactions.push(("fund".to_string(), predicate.fund(10_000).await?));
+    transactions.push((
+        "spend".to_string(),
+        predicate.spend(setup.wallet.address(), 1_000).await?,
+    ));
+    if let Some(outcome) = predicate.sweep().await? {
+        transactions.push(("sweep".to_string(), outcome));
+    }
+
+    let balance = predicate.balance().await?;
+    if balance != 0 {
+        return Err(eyre!("the predicate still holds {balance} after the sweep"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use color_eyre::{eyre::eyre, Result};
+use fuels::accounts::ViewOnlyAccount;
+
+use crate::{baseline::Costs, fees::ExpectedFee, outcome::TxOutcome, setup::Setup};
+
+/// Funds and sweeps a fresh wallet, checking the fee charged.
+pub async fn run(setup: &Setup, transactions: &mut Vec<(String, TxOutcome)>) -> Result<()> {
+    let wallet = setup.fresh_wallet_named("suite/transfers", 10_000).await?;
+    let outcome = wallet
+        .sweep()
+        .await?
+        .ok_or_else(|| eyre!("the fresh wallet had nothing to sweep"))?;
+    transactions.push(("sweep".to_string(), outcome.clone()));
+    ExpectedFee::assert_matches(setup.wallet.try_provider()?, &outcome, 0).await?;
+
+    let mut costs = Costs::named(&setup.baseline, "transfers");
+    costs.record("sweep", &outcome);
+    costs.finish()
+}
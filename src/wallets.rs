@@ -0,0 +1,190 @@
+use std::ops::Deref;
+
+use color_eyre::{eyre::eyre, Result, Section};
+use fuels::{
+    accounts::{provider::Provider, Account, ViewOnlyAccount},
+    crypto::{Hasher, SecretKey},
+    types::transaction::TxPolicies,
+};
+
+use crate::{
+    cleanup::{self, Holding},
+    helpers,
+    outcome::TxOutcome,
+    retry,
+    setup::{wallet_from_key, Wallet, SHARED_RUNTIME_THREAD},
+};
+
+/// A wallet derived from the master key and the name of the test using it, so that every test
+/// spends its own coins. Funds left over are sent back to the master wallet by
+/// [`FreshWallet::sweep`].
+///
+/// Sweeping on drop is only possible when running on a multi-threaded runtime
+/// (`#[tokio::test(flavor = "multi_thread")]`). Otherwise the funds stay where they are and are
+/// reused the next time a wallet with the same name is requested.
+#[derive(Debug)]
+pub struct FreshWallet {
+    name: String,
+    wallet: Wallet,
+    funder: Wallet,
+    swept: bool,
+}
+
+impl FreshWallet {
+    pub(crate) async fn fund(
+        funder: &Wallet,
+        master_key: &SecretKey,
+        name: &str,
+        amount: u64,
+    ) -> Result<Self> {
+        let provider = funder.try_provider()?.clone();
+        let wallet = derive_wallet(master_key, name, provider.clone());
+        // before funding, so that a run killed mid-transfer still leaves a trace
+        cleanup::record(&provider, holding(name)).await?;
+
+        let policy = retry::policy();
+        let base_asset_id = *policy
+            .query("consensus parameters query", || async {
+                Ok(provider.consensus_parameters().await?)
+            })
+            .await?
+            .base_asset_id();
+        let balance = policy
+            .query(&format!("balance query of '{name}'"), || async {
+                Ok(wallet.get_asset_balance(&base_asset_id).await?)
+            })
+            .await?;
+
+        if balance < amount {
+            funder
+                .transfer(
+                    wallet.address(),
+                    amount - balance,
+                    base_asset_id,
+                    TxPolicies::default(),
+                )
+                .await?;
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            wallet,
+            funder: funder.clone(),
+            swept: false,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn wallet(&self) -> &Wallet {
+        &self.wallet
+    }
+
+    /// Sends every asset held by the wallet back to the master wallet. Returns the outcome of the
+    /// transfer, if anything was sent.
+    pub async fn sweep(mut self) -> Result<Option<TxOutcome>> {
+        self.swept = true;
+
+        sweep(&self.wallet, &self.funder, &self.name).await
+    }
+}
+
+impl Deref for FreshWallet {
+    type Target = Wallet;
+
+    fn deref(&self) -> &Self::Target {
+        &self.wallet
+    }
+}
+
+impl Drop for FreshWallet {
+    fn drop(&mut self) {
+        if self.swept {
+            return;
+        }
+
+        let address = self.wallet.address();
+        let runtime = tokio::runtime::Handle::try_current()
+            .ok()
+            .filter(|handle| handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread);
+
+        let Some(runtime) = runtime else {
+            eprintln!(
+                "fresh wallet '{}' ({address}) was not swept, its funds will be reused next time",
+                self.name
+            );
+            return;
+        };
+
+        let swept = tokio::task::block_in_place(|| {
+            runtime.block_on(sweep(&self.wallet, &self.funder, &self.name))
+        });
+
+        if let Err(err) = swept {
+            eprintln!(
+                "failed to sweep fresh wallet '{}' ({address}): {err:?}",
+                self.name
+            );
+        }
+    }
+}
+
+async fn sweep(wallet: &Wallet, funder: &Wallet, name: &str) -> Result<Option<TxOutcome>> {
+    let outcome = helpers::transfer_all(wallet, funder.address()).await?;
+    cleanup::forget(wallet.try_provider()?, &holding(name)).await?;
+
+    Ok(outcome)
+}
+
+fn holding(name: &str) -> Holding {
+    Holding::Wallet {
+        name: name.to_string(),
+    }
+}
+
+/// The wallet `FreshWallet::fund` hands out for `name`.
+pub(crate) fn derive_wallet(master_key: &SecretKey, name: &str, provider: Provider) -> Wallet {
+    wallet_from_key(derive_key(master_key, name), provider)
+}
+
+/// Deterministic so that funds of a killed run are picked up by the next one. A digest that isn't
+/// a valid key (zero, or not below the curve order) is hashed again until one is.
+fn derive_key(master_key: &SecretKey, name: &str) -> SecretKey {
+    let mut seed = Hasher::default()
+        .chain(master_key.as_ref())
+        .chain(b"fresh-wallet")
+        .chain(name.as_bytes())
+        .finalize();
+
+    loop {
+        match SecretKey::try_from(seed.as_ref()) {
+            Ok(key) => return key,
+            Err(_) => seed = Hasher::hash(seed),
+        }
+    }
+}
+
+/// Threads that are named but run no test: the main thread, and the workers of tokio runtimes,
+/// e.g. of a `tokio::spawn`ed task or of the shared setup. The default name of the workers
+/// depends on the tokio version.
+const NON_TEST_THREADS: [&str; 4] = [
+    "main",
+    "tokio-rt-worker",
+    "tokio-runtime-worker",
+    SHARED_RUNTIME_THREAD,
+];
+
+/// libtest runs every test on a thread named after it.
+pub fn current_test_name() -> Result<String> {
+    let thread = std::thread::current();
+
+    match thread.name() {
+        Some(name) if !NON_TEST_THREADS.contains(&name) => Ok(name.to_string()),
+        _ => Err(
+            eyre!("not running on a test thread, cannot tell the test name")
+                .suggestion("use `Setup::fresh_wallet_named` to name the wallet explicitly"),
+        ),
+    }
+}
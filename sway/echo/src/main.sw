@@ -0,0 +1,37 @@
+contract;
+
+struct Point {
+    x: u64,
+}
+
+enum EchoError {
+    Zero: (),
+}
+
+abi Echo {
+    #[payable]
+    fn echo(value: u64) -> u64;
+
+    fn echo_point(point: Point) -> Point;
+
+    fn echo_nonzero(value: u64) -> u64;
+}
+
+impl Echo for Contract {
+    #[payable]
+    fn echo(value: u64) -> u64 {
+        log(value);
+
+        value
+    }
+
+    fn echo_point(point: Point) -> Point {
+        point
+    }
+
+    fn echo_nonzero(value: u64) -> u64 {
+        require(value != 0, EchoError::Zero);
+
+        value
+    }
+}
@@ -0,0 +1,10 @@
+predicate;
+
+configurable {
+    // only there to change the address, so that fixtures don't share their coins
+    SLOT: u64 = 0,
+}
+
+fn main(slot: u64) -> bool {
+    slot == SLOT
+}
@@ -0,0 +1,78 @@
+fuel_e2e_tests::define_fuels!();
+
+use fuel_e2e_tests::{
+    baseline::{self, Baseline, BaselineConfig, BaselineMode, Costs, Tolerance},
+    json_file::JsonFile,
+    setup,
+};
+
+#[tokio::test]
+async fn costs_are_recorded_and_compared() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let dir = std::env::temp_dir().join(format!("e2e-baseline-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    // the directory is created when recording
+    let path = baseline::baseline_path(dir.join("gas-baselines"), "local");
+
+    let outcome = setup
+        .fresh_wallet(1_000)
+        .await?
+        .sweep()
+        .await?
+        .expect("had funds to sweep");
+    let finish = |mode: BaselineMode| {
+        let config = BaselineConfig {
+            mode,
+            path: path.clone(),
+        };
+        let mut costs = Costs::named(&config, "baseline");
+        costs.record("sweep", &outcome);
+        costs.finish()
+    };
+
+    // not in the baseline yet
+    let err = finish(BaselineMode::Compare).expect_err("no baseline");
+    assert!(format!("{err}").contains("new: "));
+
+    finish(BaselineMode::Record)?;
+    let entry = Baseline::load(&path)?.entries["baseline/sweep"].clone();
+    assert_eq!(entry.gas, outcome.total_gas);
+    assert_eq!(entry.fee, outcome.total_fee);
+    finish(BaselineMode::Compare)?;
+
+    // 10% cheaper in the baseline
+    let cheaper = outcome.total_gas * 10 / 11;
+    Baseline::update(&path, |baseline| {
+        baseline
+            .entries
+            .get_mut("baseline/sweep")
+            .expect("recorded")
+            .gas = cheaper;
+    })?;
+    let err = finish(BaselineMode::Compare).expect_err("the gas moved");
+    let table = format!("{err}");
+    assert!(table.contains("MOVED"), "{table}");
+    assert!(table.contains(&format!("{cheaper} -> {}", outcome.total_gas)));
+
+    Baseline::update(&path, |baseline| {
+        baseline
+            .entries
+            .get_mut("baseline/sweep")
+            .expect("recorded")
+            .tolerance = Tolerance {
+            gas: 15.0,
+            fee: 0.0,
+        };
+    })?;
+    finish(BaselineMode::Compare)?;
+
+    // recording keeps the tolerance
+    finish(BaselineMode::Record)?;
+    let entry = Baseline::load(&path)?.entries["baseline/sweep"].clone();
+    assert_eq!(entry.gas, outcome.total_gas);
+    assert_eq!(entry.tolerance.gas, 15.0);
+
+    std::fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
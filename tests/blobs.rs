@@ -0,0 +1,164 @@
+fuel_e2e_tests::define_fuels!();
+
+use std::path::PathBuf;
+
+use fuel_e2e_tests::{
+    helpers::{self, DeployOptions},
+    json_file::JsonFile,
+    registry::Registry,
+    setup::{
+        self, BlobStrategy, DeployConfig, DeployStrategy, ExistingContracts, DEFAULT_DEPLOY_SEED,
+    },
+};
+
+#[test]
+fn blob_strategies_are_parsed() {
+    assert_eq!(
+        "count:4".parse::<BlobStrategy>().ok(),
+        Some(BlobStrategy::Count(4))
+    );
+    assert_eq!(
+        "size:4096".parse::<BlobStrategy>().ok(),
+        Some(BlobStrategy::Size(4096))
+    );
+    assert_eq!(
+        "max".parse::<BlobStrategy>().ok(),
+        Some(BlobStrategy::MaxAllowed)
+    );
+
+    for invalid in ["count:0", "size:12", "size", "3", "min"] {
+        assert!(invalid.parse::<BlobStrategy>().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn words_per_blob_respects_the_max_contract_size() -> color_eyre::Result<()> {
+    // 100 words, up to 40 words per contract
+    let (contract_size, max_contract_size) = (800, 320);
+
+    assert_eq!(
+        BlobStrategy::Count(3).words_per_blob(contract_size, max_contract_size)?,
+        34
+    );
+    assert_eq!(
+        BlobStrategy::Size(80).words_per_blob(contract_size, max_contract_size)?,
+        10
+    );
+    assert_eq!(
+        BlobStrategy::MaxAllowed.words_per_blob(contract_size, max_contract_size)?,
+        40
+    );
+
+    assert!(BlobStrategy::Count(2)
+        .words_per_blob(contract_size, max_contract_size)
+        .is_err());
+    assert!(BlobStrategy::Size(328)
+        .words_per_blob(contract_size, max_contract_size)
+        .is_err());
+
+    Ok(())
+}
+
+/// A contract of `words` words that only returns, in a directory of its own.
+fn write_contract(test: &str, words: usize) -> color_eyre::Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("e2e-blobs-{}-{test}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+
+    // `ret $one`, two instructions per word
+    let code = [0x24, 0x40, 0x00, 0x00].repeat(words * 2);
+    let bin = dir.join(format!("contract_{words}.bin"));
+    std::fs::write(&bin, code)?;
+    std::fs::write(
+        dir.join(format!("contract_{words}-storage_slots.json")),
+        "[]",
+    )?;
+
+    Ok(bin)
+}
+
+#[tokio::test]
+async fn loader_is_split_according_to_the_strategy() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let bin = write_contract("split", 16)?;
+    let bin = bin.to_str().expect("utf-8 path");
+
+    for (strategy, blobs) in [
+        (BlobStrategy::Count(4), 4),
+        (BlobStrategy::Size(8), 16),
+        (BlobStrategy::MaxAllowed, 1),
+    ] {
+        let salt: [u8; 32] = rand::random();
+        let deployed = helpers::deploy_blobbed(
+            bin,
+            &setup.wallet,
+            &DeployOptions::default(),
+            salt,
+            strategy,
+        )
+        .await?;
+
+        assert_eq!(deployed.blob_ids.len(), blobs, "{strategy}");
+    }
+
+    std::fs::remove_dir_all(std::path::Path::new(bin).parent().expect("in a dir"))?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn loaders_reuse_the_uploaded_blobs() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let bin = write_contract("reuse", 16)?;
+    let dir = bin.parent().expect("in a dir").to_path_buf();
+    let bin = bin.to_str().expect("utf-8 path");
+
+    let registry = dir.join("deployments.json");
+    let deploy_config = DeployConfig {
+        force_deploy: false,
+        seed: DEFAULT_DEPLOY_SEED.to_string(),
+        strategy: DeployStrategy::LoaderReusingBlobs {
+            blob_size: BlobStrategy::Count(4),
+        },
+        existing: ExistingContracts::default(),
+        registry: Some(registry.clone()),
+    };
+
+    let first = helpers::deploy(
+        &setup.wallet,
+        deploy_config.clone(),
+        bin,
+        &DeployOptions::default(),
+    )
+    .await?;
+    let second =
+        helpers::deploy(&setup.wallet, deploy_config, bin, &DeployOptions::default()).await?;
+    assert_ne!(first.contract_id, second.contract_id);
+
+    let registry = Registry::load(&registry)?;
+    let [first, second] = registry.deployments.as_slice() else {
+        panic!("expected two deployments, got {:?}", registry.deployments);
+    };
+    assert_eq!(first.blob_ids.len(), 4);
+    assert_eq!(first.blob_ids, second.blob_ids);
+    assert!(first.tx_id.is_some() && second.tx_id.is_some());
+
+    // every blob is on chain already, only the loader gets deployed
+    let salt: [u8; 32] = rand::random();
+    let third = helpers::deploy_blobbed(
+        bin,
+        &setup.wallet,
+        &DeployOptions::default(),
+        salt,
+        BlobStrategy::Count(4),
+    )
+    .await?;
+    assert!(third.newly_deployed);
+    assert_eq!(third.blobs_uploaded, 0);
+    let outcome = third.outcome.expect("newly deployed");
+    assert!(outcome.is_success());
+    assert!(outcome.block_height > 0);
+
+    std::fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+fuel_e2e_tests::define_fuels!();
+
+use fuel_e2e_tests::{
+    cleanup::{self, CleanupRegistry, Holding, ReclaimScope},
+    json_file::JsonFile,
+    predicates::{PredicateFixture, PredicateOptions, OPEN_PREDICATE_BIN},
+    setup,
+};
+use fuels::{accounts::ViewOnlyAccount, types::ContractId};
+
+// a single test since the registry path is set per process
+#[tokio::test]
+async fn abandoned_holdings_are_reclaimed() -> color_eyre::Result<()> {
+    let dir = std::env::temp_dir().join(format!("e2e-cleanup-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("cleanup.json");
+    std::env::set_var("CLEANUP_REGISTRY", &path);
+
+    let setup = setup::init().await?;
+    assert_eq!(cleanup::path(), Some(path.clone()));
+    let provider = setup.wallet.try_provider()?;
+    let base_asset_id = *provider.consensus_parameters().await?.base_asset_id();
+
+    let predicate = PredicateFixture::load(
+        OPEN_PREDICATE_BIN,
+        &setup.wallet,
+        PredicateOptions::open(2)?,
+    )
+    .await?;
+    predicate.fund(10_000).await?;
+
+    let wallet = setup
+        .fresh_wallet_named("abandoned_holdings", 10_000)
+        .await?;
+
+    let registry = CleanupRegistry::load(&path)?;
+    assert_eq!(registry.entries.len(), 2);
+    assert!(registry
+        .entries
+        .iter()
+        .all(|entry| entry.recorded_by == std::process::id()));
+
+    // still in use as far as the registry can tell
+    let report = cleanup::reclaim(&setup, ReclaimScope::Abandoned).await?;
+    assert!(report.reclaimed.is_empty());
+
+    // as if left behind by a run killed long ago
+    CleanupRegistry::update(&path, |registry| {
+        for entry in &mut registry.entries {
+            entry.recorded_by = 0;
+            entry.recorded_at = 0;
+        }
+    })?;
+    let report = cleanup::reclaim(&setup, ReclaimScope::Abandoned).await?;
+    assert_eq!(report.reclaimed.len(), 2);
+    assert!(report.kept.is_empty());
+    assert!(CleanupRegistry::load(&path)?.entries.is_empty());
+    assert_eq!(predicate.balance().await?, 0);
+    assert_eq!(wallet.get_asset_balance(&base_asset_id).await?, 0);
+
+    let deposit = Holding::Deposit {
+        contract_id: ContractId::from([1; 32]),
+        reclaimer: "abandoned_holdings".to_string(),
+    };
+    cleanup::record(provider, deposit.clone()).await?;
+
+    let report = cleanup::reclaim(&setup, ReclaimScope::ThisProcess).await?;
+    assert_eq!(report.kept.len(), 1);
+    assert!(report.kept[0].1.contains("no reclaimer"));
+
+    cleanup::register_reclaimer("abandoned_holdings", |_, _| Box::pin(async { Ok(()) }));
+    let report = cleanup::reclaim(&setup, ReclaimScope::All).await?;
+    assert_eq!(report.reclaimed.len(), 1);
+    assert_eq!(report.reclaimed[0].0, deposit);
+    assert!(CleanupRegistry::load(&path)?.entries.is_empty());
+
+    // nothing to sweep, but still forgotten
+    wallet.sweep().await?;
+    std::fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
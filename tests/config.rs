@@ -0,0 +1,236 @@
+use fuel_e2e_tests::{
+    compat::SDK_FEATURE,
+    config::{Config, ConfigFile, Flags, Profile, Source, TargetChain},
+    retry::{ErrorClass, ErrorClasses},
+    setup::{BlobStrategy, DeployStrategy, ExistingContracts},
+};
+
+#[test]
+fn profiles_are_parsed() -> color_eyre::Result<()> {
+    let file = ConfigFile::parse(
+        r#"
+        [profile.local-blobs]
+        target-chain = "local"
+        deploy-strategy = "loader-reusing-blobs"
+        existing-contracts = { counter = "0xabababababababababababababababababababababababababababababababab" }
+
+        [profile.staging]
+        target-chain = "custom"
+        node-url = "http://127.0.0.1:4000"
+        retry-attempts = 5
+        retry-on = ["timeout", "unavailable"]
+        "#,
+    )?;
+
+    let local_blobs = file.profile("local-blobs")?;
+    assert_eq!(local_blobs.target_chain, Some(TargetChain::Local));
+    assert_eq!(
+        local_blobs.deploy_strategy,
+        Some(DeployStrategy::LoaderReusingBlobs {
+            blob_size: BlobStrategy::default()
+        })
+    );
+    assert_eq!(local_blobs.force_deploy, None);
+    let existing = local_blobs
+        .existing_contracts
+        .as_ref()
+        .expect("has existing contracts");
+    assert_eq!(
+        existing.get("sway/counter/out/release/counter.bin"),
+        Some([0xab; 32].into())
+    );
+    assert_eq!(existing.get("liquidity_pool.bin"), None);
+
+    let staging = file.profile("staging")?;
+    assert_eq!(staging.target_chain, Some(TargetChain::Custom));
+    assert_eq!(staging.node_url.as_deref(), Some("http://127.0.0.1:4000"));
+    assert_eq!(staging.retry_attempts, Some(5));
+    assert_eq!(
+        staging.retry_on,
+        Some(ErrorClasses(vec![
+            ErrorClass::Timeout,
+            ErrorClass::Unavailable
+        ]))
+    );
+
+    assert!(file.profile("testnet").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn unknown_keys_are_rejected() {
+    let err = ConfigFile::parse(
+        r#"
+        [profile.local]
+        target-chain = "local"
+        deploy-strategies = "loader"
+        "#,
+    )
+    .expect_err("should reject unknown key");
+
+    let msg = format!("{err:?}");
+    assert!(msg.contains("unknown field `deploy-strategies`"), "{msg}");
+    assert!(msg.contains("line 4"), "{msg}");
+}
+
+#[test]
+fn the_shipped_config_is_valid() -> color_eyre::Result<()> {
+    ConfigFile::load(fuel_e2e_tests::config::DEFAULT_CONFIG_PATH)?;
+
+    Ok(())
+}
+
+#[test]
+fn deploy_strategies_are_parsed() {
+    let contract_id = format!("0x{}", "ab".repeat(32));
+
+    assert_eq!(
+        "normal".parse::<DeployStrategy>().ok(),
+        Some(DeployStrategy::Normal)
+    );
+    assert_eq!(
+        "loader"
+            .parse::<DeployStrategy>()
+            .ok()
+            .map(|strategy| strategy.with_blob_size(BlobStrategy::MaxAllowed)),
+        Some(DeployStrategy::Loader {
+            blob_size: BlobStrategy::MaxAllowed
+        })
+    );
+
+    for invalid in [
+        "blobs",
+        "existing",
+        &format!("existing:{contract_id}"),
+        "loader:3",
+    ] {
+        assert!(invalid.parse::<DeployStrategy>().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn existing_contracts_are_parsed() -> color_eyre::Result<()> {
+    let contract_id = format!("0x{}", "ab".repeat(32));
+
+    let existing: ExistingContracts =
+        format!("counter={contract_id}, liquidity_pool = {contract_id}").parse()?;
+    assert_eq!(existing.get("counter.bin"), Some([0xab; 32].into()));
+    assert_eq!(existing.get("liquidity_pool.bin"), Some([0xab; 32].into()));
+    assert_eq!(
+        existing.to_string(),
+        format!("counter={contract_id},liquidity_pool={contract_id}")
+    );
+    assert!("".parse::<ExistingContracts>()?.is_empty());
+
+    for invalid in ["counter", "counter=0x12", &contract_id] {
+        assert!(invalid.parse::<ExistingContracts>().is_err(), "{invalid}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn force_deploy_overrides_the_seed() -> color_eyre::Result<()> {
+    let profile = Profile {
+        target_chain: Some(TargetChain::Local),
+        force_deploy: Some(true),
+        deploy_seed: Some("pinned".to_string()),
+        ..Profile::default()
+    };
+    let config = Config::resolve(Some("forced".to_string()), profile)?;
+    // FORCE_DEPLOY=false in the env takes precedence over the profile
+    if !config.deploy_config.force_deploy {
+        return Ok(());
+    }
+
+    assert_ne!(config.deploy_config.seed, "pinned");
+    let report = config.report()?;
+    let seed = report
+        .settings
+        .iter()
+        .find(|setting| setting.name == "DEPLOY_SEED")
+        .expect("reports the seed");
+    assert_eq!(seed.value.as_ref(), Some(&config.deploy_config.seed));
+    assert_eq!(seed.source, Source::ForceDeploy);
+
+    Ok(())
+}
+
+#[test]
+fn baselines_are_kept_per_sdk_feature_and_chain() -> color_eyre::Result<()> {
+    // the env takes precedence over the profile
+    if std::env::var_os("TARGET_CHAIN").is_some() || std::env::var_os("GAS_BASELINE_DIR").is_some()
+    {
+        return Ok(());
+    }
+
+    let profile = Profile {
+        target_chain: Some(TargetChain::Local),
+        gas_baseline_dir: Some("baselines".to_string()),
+        ..Profile::default()
+    };
+    let config = Config::resolve(Some("baselines".to_string()), profile)?;
+
+    assert_eq!(
+        config.baseline.path,
+        std::path::Path::new("baselines").join(format!("{SDK_FEATURE}-local.json"))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn flags_take_precedence_over_the_env() -> color_eyre::Result<()> {
+    let flags = Flags::from([
+        ("TARGET_CHAIN".to_string(), "local".to_string()),
+        ("DEPLOY_STRATEGY".to_string(), "loader".to_string()),
+        (
+            "EXISTING_CONTRACTS".to_string(),
+            format!("counter=0x{}", "12".repeat(32)),
+        ),
+    ]);
+    let config = Config::resolve_with_flags(None, Profile::default(), &flags)?;
+
+    assert_eq!(config.target_chain, TargetChain::Local);
+    assert_eq!(config.deploy_config.strategy.to_string(), "loader");
+    assert_eq!(
+        config.deploy_config.existing.get("sway/counter.bin"),
+        Some([0x12; 32].into())
+    );
+    let report = config.report()?;
+    for name in ["TARGET_CHAIN", "DEPLOY_STRATEGY", "EXISTING_CONTRACTS"] {
+        let setting = report
+            .settings
+            .iter()
+            .find(|setting| setting.name == name)
+            .expect("reported");
+        assert_eq!(setting.source, Source::Flag, "{name}");
+    }
+
+    let flags = Flags::from([("TARGET_CHAIN".to_string(), "moon".to_string())]);
+    let err = Config::resolve_with_flags(None, Profile::default(), &flags).expect_err("moon");
+    assert!(format!("{err}").contains("invalid target chain value: moon"));
+
+    Ok(())
+}
+
+#[test]
+fn retry_attempts_must_be_positive() {
+    // the env takes precedence over the profile
+    if std::env::var_os("RETRY_ATTEMPTS").is_some() {
+        return;
+    }
+
+    let profile = Profile {
+        target_chain: Some(TargetChain::Local),
+        retry_attempts: Some(0),
+        ..Profile::default()
+    };
+    let err = Config::resolve(Some("no-retries".to_string()), profile).expect_err("0 attempts");
+
+    assert!(
+        format!("{err}").contains("invalid value for RETRY_ATTEMPTS: 0"),
+        "{err}"
+    );
+}
@@ -0,0 +1,261 @@
+fuel_e2e_tests::define_fuels!();
+
+use std::path::PathBuf;
+
+use fuel_e2e_tests::{
+    helpers::{self, DeployOptions},
+    json_file::JsonFile,
+    registry::Registry,
+    setup::{self, DeployConfig, DeployStrategy},
+};
+use fuels::{accounts::ViewOnlyAccount, core::Configurables, tx::StorageSlot, types::ContractId};
+
+/// A contract that only returns, along with an empty storage slots file, in a directory of its
+/// own.
+fn write_contract(test: &str) -> color_eyre::Result<PathBuf> {
+    let dir =
+        std::env::temp_dir().join(format!("e2e-deploy-options-{}-{test}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+
+    // `ret $one`, two instructions per word
+    let code = [0x24, 0x40, 0x00, 0x00].repeat(8);
+    let bin = dir.join("contract.bin");
+    std::fs::write(&bin, code)?;
+    std::fs::write(dir.join("contract-storage_slots.json"), "[]")?;
+
+    Ok(bin)
+}
+
+#[tokio::test]
+async fn variants_of_a_binary_are_distinct_instances() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let bin = write_contract("variants")?;
+    let dir = bin.parent().expect("in a dir").to_path_buf();
+    let bin = bin.to_str().expect("utf-8 path");
+
+    let slots_file = dir.join("seeded-storage_slots.json");
+    std::fs::write(
+        &slots_file,
+        format!(
+            r#"[{{"key": "{}", "value": "{}"}}]"#,
+            "01".repeat(32),
+            "02".repeat(32)
+        ),
+    )?;
+
+    let deploy_config = DeployConfig {
+        registry: None,
+        ..setup.deploy_config.clone()
+    };
+    let deploy = |options: DeployOptions| {
+        let deploy_config = deploy_config.clone();
+        let wallet = setup.wallet.clone();
+        async move {
+            let deployed = helpers::deploy(&wallet, deploy_config, bin, &options).await?;
+            color_eyre::Result::<_>::Ok(deployed.contract_id)
+        }
+    };
+
+    let plain = deploy(DeployOptions::default()).await?;
+    let from_file = deploy(DeployOptions {
+        storage_slots: Some(slots_file),
+        ..DeployOptions::default()
+    })
+    .await?;
+    let overridden = deploy(DeployOptions {
+        storage_overrides: vec![StorageSlot::new([1; 32].into(), [3; 32].into())],
+        ..DeployOptions::default()
+    })
+    .await?;
+    // the second word is never executed, patching it changes the code but not the behavior
+    let configured = deploy(DeployOptions {
+        configurables: Configurables::new(vec![(8, vec![0x00; 8])]),
+        ..DeployOptions::default()
+    })
+    .await?;
+
+    let mut ids = vec![&plain, &from_file, &overridden, &configured];
+    ids.sort_by_key(|id| id.to_string());
+    ids.dedup();
+    assert_eq!(ids.len(), 4);
+
+    let missing_file = deploy(DeployOptions {
+        storage_slots: Some(dir.join("missing-storage_slots.json")),
+        ..DeployOptions::default()
+    })
+    .await;
+    assert!(missing_file.is_err());
+
+    std::fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn existing_contracts_are_used_as_is() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let bin = write_contract("existing")?;
+    let dir = bin.parent().expect("in a dir").to_path_buf();
+    let bin = bin.to_str().expect("utf-8 path");
+
+    let deploy_config = DeployConfig {
+        strategy: DeployStrategy::Normal,
+        registry: None,
+        ..setup.deploy_config.clone()
+    };
+    let deployed = helpers::deploy(
+        &setup.wallet,
+        deploy_config.clone(),
+        bin,
+        &DeployOptions::default(),
+    )
+    .await?
+    .contract_id;
+
+    let existing = |contract_id: ContractId| {
+        let mut deploy_config = deploy_config.clone();
+        deploy_config.existing.insert("contract", contract_id);
+        deploy_config
+    };
+    // the binary isn't even read
+    let missing = bin.replace("contract.bin", "missing/contract.bin");
+    let bound = helpers::deploy(
+        &setup.wallet,
+        existing((&deployed).into()),
+        &missing,
+        &DeployOptions::default(),
+    )
+    .await?;
+    assert_eq!(bound.contract_id, deployed);
+    assert!(!bound.newly_deployed);
+    assert!(bound.outcome.is_none());
+
+    // binaries that aren't listed are deployed as usual
+    let other = dir.join("other.bin");
+    std::fs::copy(bin, &other)?;
+    std::fs::write(dir.join("other-storage_slots.json"), "[]")?;
+    let unlisted = helpers::deploy(
+        &setup.wallet,
+        existing([7; 32].into()),
+        other.to_str().expect("utf-8 path"),
+        &DeployOptions {
+            instance: Some("unlisted".to_string()),
+            ..DeployOptions::default()
+        },
+    )
+    .await?
+    .contract_id;
+    assert_ne!(unlisted, deployed);
+    assert!(
+        setup
+            .wallet
+            .try_provider()?
+            .contract_exists(&unlisted)
+            .await?
+    );
+
+    let not_deployed = helpers::deploy(
+        &setup.wallet,
+        existing([7; 32].into()),
+        bin,
+        &DeployOptions::default(),
+    )
+    .await;
+    assert!(not_deployed.is_err());
+
+    std::fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn salts_are_derived_from_the_seed_and_the_instance() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let bin = write_contract("salts")?;
+    let dir = bin.parent().expect("in a dir").to_path_buf();
+    let bin = bin.to_str().expect("utf-8 path");
+
+    let deploy = |seed: &str, instance: Option<&str>| {
+        let deploy_config = DeployConfig {
+            strategy: DeployStrategy::Normal,
+            seed: seed.to_string(),
+            registry: None,
+            ..setup.deploy_config.clone()
+        };
+        let options = DeployOptions {
+            instance: instance.map(ToString::to_string),
+            ..DeployOptions::default()
+        };
+        let wallet = setup.wallet.clone();
+        async move {
+            let deployed = helpers::deploy(&wallet, deploy_config, bin, &options).await?;
+            color_eyre::Result::<_>::Ok(deployed.contract_id)
+        }
+    };
+
+    // named after the running test by default
+    let this_test = deploy("a", None).await?;
+    assert_eq!(
+        this_test,
+        deploy(
+            "a",
+            Some("salts_are_derived_from_the_seed_and_the_instance")
+        )
+        .await?
+    );
+
+    let other_test = deploy("a", Some("other_test")).await?;
+    let other_seed = deploy("b", None).await?;
+    assert_ne!(this_test, other_test);
+    assert_ne!(this_test, other_seed);
+    assert_ne!(other_test, other_seed);
+
+    std::fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn recorded_instances_are_reused() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let bin = write_contract("recorded")?;
+    let dir = bin.parent().expect("in a dir").to_path_buf();
+    let bin = bin.to_str().expect("utf-8 path");
+    let registry = dir.join("deployments.json");
+
+    let deploy = |instance: &str, registry: Option<PathBuf>| {
+        let deploy_config = DeployConfig {
+            strategy: DeployStrategy::Normal,
+            registry,
+            ..setup.deploy_config.clone()
+        };
+        let options = DeployOptions {
+            instance: Some(instance.to_string()),
+            ..DeployOptions::default()
+        };
+        let wallet = setup.wallet.clone();
+        async move {
+            let deployed = helpers::deploy(&wallet, deploy_config, bin, &options).await?;
+            color_eyre::Result::<_>::Ok(deployed.contract_id)
+        }
+    };
+    let record_as = |contract_id: ContractId| {
+        Registry::update(&registry, |registry| {
+            registry.deployments[0].contract_id = contract_id;
+        })
+    };
+
+    let recorded = deploy("a", Some(registry.clone())).await?;
+    let other = deploy("b", None).await?;
+
+    // whatever the registry says is used as long as it is on chain
+    record_as((&other).into())?;
+    assert_eq!(deploy("a", Some(registry.clone())).await?, other);
+
+    record_as([7; 32].into())?;
+    assert_eq!(deploy("a", Some(registry.clone())).await?, recorded);
+
+    std::fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
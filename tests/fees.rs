@@ -0,0 +1,28 @@
+fuel_e2e_tests::define_fuels!();
+
+use fuel_e2e_tests::{fees::ExpectedFee, setup};
+use fuels::accounts::ViewOnlyAccount;
+
+#[tokio::test]
+async fn transfer_fees_match_the_fee_schedule() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let provider = setup.wallet.try_provider()?;
+
+    let fresh = setup.fresh_wallet(1_000).await?;
+    let outcome = fresh.sweep().await?.expect("had funds to sweep");
+
+    let expected = ExpectedFee::assert_matches(provider, &outcome, 0).await?;
+    assert!(expected.metered_bytes > 0);
+    assert!(expected.min_gas > 0);
+    // a script, if an empty one
+    assert_eq!(expected.min_gas + expected.script_gas, outcome.total_gas);
+
+    let mut overcharged = outcome.clone();
+    overcharged.total_fee = expected.fee + 2;
+    assert!(ExpectedFee::assert_matches(provider, &overcharged, 1)
+        .await
+        .is_err());
+    ExpectedFee::assert_matches(provider, &overcharged, 2).await?;
+
+    Ok(())
+}
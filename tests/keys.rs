@@ -0,0 +1,51 @@
+fuel_e2e_tests::define_fuels!();
+
+use fuel_e2e_tests::keys::{KeySource, DEFAULT_DERIVATION_PATH};
+use fuels::crypto::SecretKey;
+
+const PHRASE: &str =
+    "oblige salon price punch saddle immune slogan rare snap desert retire surprise";
+
+#[test]
+fn keystore_can_hold_a_private_key_or_a_mnemonic() -> color_eyre::Result<()> {
+    let dir = std::env::temp_dir().join(format!("e2e-keystore-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let mut rng = rand_08::thread_rng();
+
+    let from_mnemonic = KeySource::Mnemonic {
+        phrase: PHRASE.to_string(),
+        path: DEFAULT_DERIVATION_PATH.to_string(),
+    }
+    .secret_key()?;
+
+    let mnemonic_keystore = eth_keystore::encrypt_key(&dir, &mut rng, PHRASE, "pass", None)?;
+    let from_mnemonic_keystore = KeySource::Keystore {
+        file: dir.join(mnemonic_keystore),
+        password: "pass".to_string(),
+        path: DEFAULT_DERIVATION_PATH.to_string(),
+    }
+    .secret_key()?;
+    assert_eq!(from_mnemonic_keystore, from_mnemonic);
+
+    let key = SecretKey::new_from_mnemonic_phrase_with_path(PHRASE, "m/44'/60'/0'/0/0")?;
+    let key_keystore = eth_keystore::encrypt_key(&dir, &mut rng, *key, "pass", None)?;
+    let from_key_keystore = KeySource::Keystore {
+        file: dir.join(&key_keystore),
+        password: "pass".to_string(),
+        path: DEFAULT_DERIVATION_PATH.to_string(),
+    }
+    .secret_key()?;
+    assert_eq!(from_key_keystore, key);
+
+    let wrong_password = KeySource::Keystore {
+        file: dir.join(key_keystore),
+        password: "wrong".to_string(),
+        path: DEFAULT_DERIVATION_PATH.to_string(),
+    }
+    .secret_key();
+    assert!(wrong_password.is_err());
+
+    std::fs::remove_dir_all(dir)?;
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+fuel_e2e_tests::define_fuels!();
+
+use std::time::Duration;
+
+use fuel_core_chain_config::ChainConfig;
+use fuel_e2e_tests::{
+    config::{Config, ConfigFile, Profile, TargetChain},
+    local_node::{BlockTime, LocalNodeConfig, MASTER_WALLET_COINS},
+};
+use fuels::{accounts::ViewOnlyAccount, types::AssetId};
+
+/// `None` if TARGET_CHAIN points elsewhere, the local node settings are ignored then and the
+/// calling test is skipped.
+fn local_setup(local_node: LocalNodeConfig) -> color_eyre::Result<Option<Config>> {
+    let profile = Profile {
+        target_chain: Some(TargetChain::Local),
+        local_node: Some(local_node),
+        ..Profile::default()
+    };
+    let config = Config::resolve(Some("test".to_string()), profile)?;
+
+    if config.target_chain != TargetChain::Local {
+        eprintln!(
+            "skipping {}: TARGET_CHAIN={} overrides the local node",
+            std::thread::current().name().unwrap_or("test"),
+            config.target_chain
+        );
+        return Ok(None);
+    }
+
+    Ok(Some(config))
+}
+
+#[test]
+fn local_node_settings_are_parsed() -> color_eyre::Result<()> {
+    let file = ConfigFile::parse(
+        r#"
+        [profile.local-slow]
+        target-chain = "local"
+
+        [profile.local-slow.local-node]
+        chain-config = "chain-configs/testnet"
+        max-gas-per-tx = 1000
+        block-time = "2s"
+        base-asset-id = "0x0101010101010101010101010101010101010101010101010101010101010101"
+        "#,
+    )?;
+
+    let local_node = file
+        .profile("local-slow")?
+        .local_node
+        .clone()
+        .expect("has local node settings");
+    assert_eq!(
+        local_node.chain_config.as_deref(),
+        Some("chain-configs/testnet")
+    );
+    assert_eq!(local_node.max_gas_per_tx, Some(1000));
+    assert_eq!(local_node.contract_max_size, None);
+    assert_eq!(
+        local_node.block_time,
+        Some(BlockTime::Interval(Duration::from_secs(2)))
+    );
+    assert_eq!(local_node.base_asset_id, Some(AssetId::new([1; 32])));
+
+    assert!("2 seconds".parse::<BlockTime>().is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn local_node_uses_the_overridden_consensus_parameters() -> color_eyre::Result<()> {
+    let base_asset_id = AssetId::new([1; 32]);
+    let Some(config) = local_setup(LocalNodeConfig {
+        max_gas_per_tx: Some(12_345_678),
+        contract_max_size: Some(200_000),
+        base_asset_id: Some(base_asset_id),
+        ..LocalNodeConfig::default()
+    })?
+    else {
+        return Ok(());
+    };
+
+    let wallet = config.setup().await?.wallet;
+    let consensus_parameters = wallet.try_provider()?.consensus_parameters().await?;
+
+    assert_eq!(
+        consensus_parameters.tx_params().max_gas_per_tx(),
+        12_345_678
+    );
+    assert_eq!(
+        consensus_parameters.contract_params().contract_max_size(),
+        200_000
+    );
+    assert_eq!(*consensus_parameters.base_asset_id(), base_asset_id);
+    assert_eq!(
+        wallet.get_coins(base_asset_id).await?.len() as u64,
+        MASTER_WALLET_COINS
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn local_node_loads_the_chain_config_dir() -> color_eyre::Result<()> {
+    let dir = std::env::temp_dir().join(format!("e2e-chain-config-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+
+    let mut chain_config = ChainConfig::local_testnet();
+    chain_config.consensus_parameters.set_chain_id(1337.into());
+    chain_config
+        .write(dir.join("chain_config.json"))
+        .map_err(|e| color_eyre::eyre::eyre!("{e:?}"))?;
+
+    let Some(config) = local_setup(LocalNodeConfig {
+        chain_config: Some(dir.display().to_string()),
+        ..LocalNodeConfig::default()
+    })?
+    else {
+        return Ok(());
+    };
+
+    let wallet = config.setup().await?.wallet;
+    let chain_id = wallet
+        .try_provider()?
+        .consensus_parameters()
+        .await?
+        .chain_id();
+    std::fs::remove_dir_all(&dir)?;
+
+    assert_eq!(chain_id, 1337.into());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn local_node_mirrors_remote_consensus_parameters() -> color_eyre::Result<()> {
+    // stands in for the remote chain
+    let Some(remote) = local_setup(LocalNodeConfig {
+        max_gas_per_tx: Some(7_654_321),
+        ..LocalNodeConfig::default()
+    })?
+    else {
+        return Ok(());
+    };
+    let remote = remote.setup().await?.wallet;
+    let remote_url = remote.try_provider()?.url().to_string();
+
+    let file = std::env::temp_dir().join(format!(
+        "e2e-consensus-parameters-{}.json",
+        std::process::id()
+    ));
+    let mirroring = |mirror: &str| LocalNodeConfig {
+        mirror: Some(mirror.to_string()),
+        consensus_parameters: Some(file.display().to_string()),
+        ..LocalNodeConfig::default()
+    };
+
+    assert_eq!(max_gas_per_tx(mirroring(&remote_url)).await?, 7_654_321);
+    assert!(file.exists());
+
+    // the saved parameters are used from now on, the remote isn't contacted anymore
+    assert_eq!(
+        max_gas_per_tx(mirroring("http://127.0.0.1:1")).await?,
+        7_654_321
+    );
+
+    std::fs::remove_file(&file)?;
+
+    Ok(())
+}
+
+async fn max_gas_per_tx(local_node: LocalNodeConfig) -> color_eyre::Result<u64> {
+    let config = local_setup(local_node)?.expect("targets the local node");
+    let wallet = config.setup().await?.wallet;
+    let consensus_parameters = wallet.try_provider()?.consensus_parameters().await?;
+
+    Ok(consensus_parameters.tx_params().max_gas_per_tx())
+}
@@ -0,0 +1,66 @@
+fuel_e2e_tests::define_fuels!();
+
+use std::time::Duration;
+
+use fuel_e2e_tests::{
+    outcome::{ProviderExt, TxStatusError},
+    setup,
+};
+use fuels::{accounts::ViewOnlyAccount, types::Bytes32};
+
+#[tokio::test]
+async fn final_status_of_a_transfer_is_reported() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let provider = setup.wallet.try_provider()?;
+
+    let fresh = setup.fresh_wallet(1_000).await?;
+    let outcome = fresh.sweep().await?.expect("had funds to sweep");
+
+    let awaited = provider
+        .await_final_status(&outcome.tx_id, Duration::from_secs(5))
+        .await?;
+    assert!(awaited.is_success());
+    assert_eq!(awaited.block_height, outcome.block_height);
+
+    assert_eq!(
+        provider.tx_block_height(&outcome.tx_id).await?,
+        outcome.block_height
+    );
+    assert_eq!(
+        provider.tx_gas_used(&outcome.tx_id).await?,
+        outcome.total_gas
+    );
+    assert_eq!(
+        provider.tx_total_fee(&outcome.tx_id).await?,
+        outcome.total_fee
+    );
+    assert_eq!(
+        provider.tx_receipts(&outcome.tx_id).await?,
+        outcome.receipts
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_transactions_are_typed_errors() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let provider = setup.wallet.try_provider()?;
+    let tx_id = Bytes32::new([9; 32]);
+    let not_found = TxStatusError::NotFound { tx_id };
+
+    let err = provider
+        .tx_receipts(&tx_id)
+        .await
+        .expect_err("no such transaction");
+    assert_eq!(err.downcast_ref::<TxStatusError>(), Some(&not_found));
+
+    // waited for, in case it is about to be submitted
+    let err = provider
+        .await_final_status(&tx_id, Duration::from_millis(300))
+        .await
+        .expect_err("no such transaction");
+    assert_eq!(err.downcast_ref::<TxStatusError>(), Some(&not_found));
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+fuel_e2e_tests::define_fuels!();
+
+use fuel_e2e_tests::{
+    json_file::JsonFile,
+    registry::{Deployment, DeploymentKind, Registry},
+};
+use fuels::types::{Bytes32, ContractId};
+
+fn deployment(contract_id: u8, last_used_at: u64) -> Deployment {
+    Deployment {
+        chain_id: 0,
+        node_url: "https://testnet.fuel.network".to_string(),
+        contract_bin: "contract.bin".to_string(),
+        bytecode_hash: Bytes32::new([1; 32]),
+        salt: [contract_id; 32].into(),
+        seed: Some("e2e".to_string()),
+        instance: Some("the_test".to_string()),
+        kind: DeploymentKind::Normal,
+        blob_ids: vec![],
+        contract_id: ContractId::new([contract_id; 32]),
+        tx_id: Some(Bytes32::new([contract_id; 32])),
+        deployed_at: Some(last_used_at),
+        last_used_at,
+    }
+}
+
+#[test]
+fn the_most_recently_used_instance_is_found() {
+    let mut registry = Registry::default();
+    registry.record(deployment(1, 100));
+    registry.record(deployment(2, 200));
+
+    let key = deployment(0, 0).key();
+    assert_eq!(
+        registry.find(&key).map(|d| d.contract_id),
+        Some(ContractId::new([2; 32]))
+    );
+
+    // reusing an instance doesn't register it twice
+    registry.record(Deployment {
+        tx_id: None,
+        deployed_at: None,
+        ..deployment(1, 300)
+    });
+    assert_eq!(registry.deployments.len(), 2);
+    assert_eq!(
+        registry.find(&key).map(|d| (d.contract_id, d.tx_id)),
+        Some((ContractId::new([1; 32]), Some(Bytes32::new([1; 32]))))
+    );
+
+    let loader_key = Deployment {
+        kind: DeploymentKind::Loader,
+        ..deployment(0, 0)
+    }
+    .key();
+    assert!(registry.find(&loader_key).is_none());
+
+    let other_instance = Deployment {
+        instance: Some("another_test".to_string()),
+        ..deployment(0, 0)
+    }
+    .key();
+    assert!(registry.find(&other_instance).is_none());
+}
+
+#[test]
+fn registry_survives_a_round_trip_and_prunes() -> color_eyre::Result<()> {
+    let path = std::env::temp_dir().join(format!("e2e-registry-{}.json", std::process::id()));
+
+    Registry::update(&path, |registry| {
+        registry.record(deployment(1, 100));
+        registry.record(deployment(2, 200));
+    })?;
+
+    let pruned = Registry::update(&path, |registry| {
+        registry.prune(|deployment| deployment.last_used_at < 150)
+    })?;
+    assert_eq!(pruned.len(), 1);
+    assert_eq!(pruned[0].contract_id, ContractId::new([1; 32]));
+
+    let registry = Registry::load(&path)?;
+    std::fs::remove_file(&path)?;
+
+    assert_eq!(registry.deployments.len(), 1);
+    assert_eq!(
+        registry.deployments[0].contract_id,
+        ContractId::new([2; 32])
+    );
+    assert_eq!(registry.deployments[0].salt, [2; 32].into());
+
+    Ok(())
+}
+
+#[test]
+fn concurrent_updates_are_all_kept() -> color_eyre::Result<()> {
+    let path = std::env::temp_dir().join(format!(
+        "e2e-registry-concurrent-{}.json",
+        std::process::id()
+    ));
+
+    // every update opens and locks the lock file anew, same as another process would
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (1..=16)
+            .map(|id| {
+                let path = &path;
+                scope.spawn(move || {
+                    Registry::update(path, |registry| registry.record(deployment(id, 0)))
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .try_for_each(|handle| handle.join().expect("no panic"))
+    })?;
+
+    let registry = Registry::load(&path)?;
+    std::fs::remove_file(&path)?;
+    std::fs::remove_file(path.with_extension("json.lock"))?;
+
+    assert_eq!(registry.deployments.len(), 16);
+
+    Ok(())
+}
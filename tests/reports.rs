@@ -0,0 +1,37 @@
+fuel_e2e_tests::define_fuels!();
+
+use fuel_e2e_tests::{report::RunReport, suites};
+
+// a single test since the report is written per process
+#[tokio::test]
+async fn suites_run_as_tests_are_reported() -> color_eyre::Result<()> {
+    let dir = std::env::temp_dir().join(format!("e2e-reports-{}", std::process::id()));
+    std::env::set_var("REPORT_DIR", &dir);
+
+    suites::run_test("transfers").await?;
+    suites::run_test("transfers").await?;
+
+    let mut written = std::fs::read_dir(&dir)?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    written.sort();
+    assert_eq!(written.len(), 2, "{written:?}");
+    let name = written[0].file_name().expect("a file").to_string_lossy();
+    assert!(
+        name.ends_with(&format!("-{}.json", std::process::id())),
+        "{name}"
+    );
+
+    let report: RunReport = serde_json::from_str(&std::fs::read_to_string(&written[0])?)?;
+    assert_eq!(report.failures(), 0);
+    assert_eq!(report.tests.len(), 2);
+    assert!(report.tests.iter().all(|test| test.name == "transfers"));
+    assert_eq!(report.tests[1].transactions[0].name, "sweep");
+
+    let junit = std::fs::read_to_string(&written[1])?;
+    assert!(junit.contains("tests=\"2\" failures=\"0\""), "{junit}");
+
+    std::fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
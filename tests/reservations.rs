@@ -0,0 +1,37 @@
+fuel_e2e_tests::define_fuels!();
+
+use fuel_e2e_tests::setup::{self, Setup};
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    types::{
+        bech32::{Bech32Address, FUEL_BECH32_HRP},
+        transaction::TxPolicies,
+    },
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_transfers_dont_pick_the_same_coins() -> color_eyre::Result<()> {
+    let Setup { wallet, .. } = setup::init().await?;
+    let base_asset_id = *wallet
+        .try_provider()?
+        .consensus_parameters()
+        .await?
+        .base_asset_id();
+
+    let mut transfers = tokio::task::JoinSet::new();
+    for i in 0..10 {
+        let wallet = wallet.clone();
+        let recipient = Bech32Address::new(FUEL_BECH32_HRP, [i; 32]);
+        transfers.spawn(async move {
+            wallet
+                .transfer(&recipient, 1, base_asset_id, TxPolicies::default())
+                .await
+        });
+    }
+
+    while let Some(transfer) = transfers.join_next().await {
+        transfer??;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+fuel_e2e_tests::define_fuels!();
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use color_eyre::eyre::eyre;
+use fuel_e2e_tests::{
+    compat::TransactionCompat,
+    outcome::TxStatusError,
+    retry::{ErrorClass, ErrorClasses, RetryPolicy, MAX_BACKOFF},
+    setup,
+};
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    types::{
+        transaction::TxPolicies,
+        transaction_builders::{BuildableTransaction, ScriptTransactionBuilder},
+        Bytes32,
+    },
+};
+
+fn policy(retry_on: &str) -> RetryPolicy {
+    RetryPolicy {
+        attempts: 3,
+        backoff: Duration::from_millis(10),
+        retry_on: retry_on.parse().expect("valid classes"),
+    }
+}
+
+#[test]
+fn error_classes_are_recognized() {
+    let cases = [
+        (
+            "io error: error sending request for url (https://testnet.fuel.network/v1/graphql)",
+            Some(ErrorClass::Connection),
+        ),
+        (
+            "io error: Server returned 502 Bad Gateway: <html>",
+            Some(ErrorClass::Unavailable),
+        ),
+        (
+            "io error: Server returned 504 Gateway Timeout",
+            Some(ErrorClass::Unavailable),
+        ),
+        ("io error: operation timed out", Some(ErrorClass::Timeout)),
+        (
+            "io error: Server returned 429 Too Many Requests",
+            Some(ErrorClass::RateLimited),
+        ),
+        ("revert: contract 0x5024290 panicked", None),
+        ("Validity(InsufficientFeeAmount)", None),
+    ];
+    for (message, class) in cases {
+        assert_eq!(ErrorClass::of(&eyre!(message)), class, "{message}");
+    }
+
+    let timeout = TxStatusError::Timeout {
+        tx_id: Bytes32::zeroed(),
+        timeout: Duration::from_secs(1),
+    };
+    assert_eq!(ErrorClass::of(&timeout.into()), None);
+
+    assert_eq!(
+        "timeout, rate-limited".parse::<ErrorClasses>().ok(),
+        Some(ErrorClasses(vec![
+            ErrorClass::Timeout,
+            ErrorClass::RateLimited
+        ]))
+    );
+    assert_eq!("".parse::<ErrorClasses>().ok(), Some(ErrorClasses(vec![])));
+    assert!("timeouts".parse::<ErrorClasses>().is_err());
+}
+
+#[test]
+fn backoff_doubles_up_to_the_max() {
+    let policy = policy("");
+
+    assert_eq!(policy.delay(1), Duration::from_millis(10));
+    assert_eq!(policy.delay(2), Duration::from_millis(20));
+    assert_eq!(policy.delay(3), Duration::from_millis(40));
+    assert_eq!(policy.delay(40), MAX_BACKOFF);
+}
+
+#[tokio::test]
+async fn only_transient_errors_are_retried() {
+    let calls = AtomicUsize::new(0);
+    let failing_twice = || async {
+        match calls.fetch_add(1, Ordering::SeqCst) {
+            0 | 1 => Err(eyre!("io error: connection reset by peer")),
+            _ => Ok(42),
+        }
+    };
+    let value = policy("connection")
+        .query("test query", failing_twice)
+        .await;
+    assert_eq!(value.ok(), Some(42));
+    assert_eq!(calls.swap(0, Ordering::SeqCst), 3);
+
+    // not in the classes retried
+    let value = policy("timeout").query("test query", failing_twice).await;
+    assert!(value.is_err());
+    assert_eq!(calls.swap(0, Ordering::SeqCst), 1);
+
+    // out of attempts
+    let always_failing = || async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err::<(), _>(eyre!("io error: Server returned 503 Service Unavailable"))
+    };
+    let err = policy("unavailable")
+        .query("test query", always_failing)
+        .await
+        .expect_err("should give up");
+    assert!(err.to_string().contains("503"));
+    assert_eq!(calls.swap(0, Ordering::SeqCst), 3);
+
+    let not_transient = || async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err::<(), _>(eyre!("Validity(InsufficientFeeAmount)"))
+    };
+    assert!(RetryPolicy::default()
+        .query("test query", not_transient)
+        .await
+        .is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn lost_submissions_are_not_resubmitted() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let wallet = setup.fresh_wallet(100_000).await?;
+    let provider = wallet.try_provider()?.clone();
+    let base_asset_id = *provider.consensus_parameters().await?.base_asset_id();
+
+    let transfer = || async {
+        let inputs = wallet
+            .get_asset_inputs_for_amount(base_asset_id, 1_000, None)
+            .await?;
+        let outputs =
+            wallet.get_asset_outputs_for_amount(setup.wallet.address(), base_asset_id, 1_000);
+        let mut tb =
+            ScriptTransactionBuilder::prepare_transfer(inputs, outputs, TxPolicies::default());
+        wallet.add_witnesses(&mut tb)?;
+        wallet.adjust_for_fee(&mut tb, 1_000).await?;
+
+        let tx = tb.build(&provider).await?;
+        let tx_id = tx.tx_id(&provider).await?;
+        color_eyre::Result::<_>::Ok((tx, tx_id))
+    };
+
+    // submitted, but the response is lost: waited for instead of being sent again
+    let (tx, tx_id) = transfer().await?;
+    let sends = AtomicUsize::new(0);
+    let outcome = policy("connection")
+        .submit(&provider, &tx_id, "test transfer", || async {
+            sends.fetch_add(1, Ordering::SeqCst);
+            provider
+                .send_transaction_and_await_commit(tx.clone())
+                .await?;
+            Err(eyre!("io error: connection reset by peer"))
+        })
+        .await?;
+    assert!(outcome.is_success());
+    assert_eq!(sends.swap(0, Ordering::SeqCst), 1);
+
+    // never reached the node: sent again
+    let (tx, tx_id) = transfer().await?;
+    let outcome = policy("connection")
+        .submit(&provider, &tx_id, "test transfer", || async {
+            if sends.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err(eyre!("io error: error sending request for url"));
+            }
+            provider
+                .send_transaction_and_await_commit(tx.clone())
+                .await?;
+            Ok(())
+        })
+        .await?;
+    assert!(outcome.is_success());
+    assert_eq!(outcome.tx_id, tx_id);
+    assert_eq!(sends.load(Ordering::SeqCst), 2);
+
+    wallet.sweep().await?;
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+fuel_e2e_tests::define_fuels!();
+
+use std::path::Path;
+
+use fuel_e2e_tests::{
+    scenario::{Scenario, DEFAULT_SCENARIO_DIR},
+    setup,
+};
+use fuels::accounts::ViewOnlyAccount;
+
+#[tokio::test]
+async fn scenario_files_pass() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+
+    for scenario in Scenario::load_dir(DEFAULT_SCENARIO_DIR)? {
+        let report = scenario.run(&setup).await?;
+        eprintln!(
+            "scenario {} passed, {} transactions",
+            report.name,
+            report.transactions.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Logs and returns its argument, see `sway/echo`.
+const ECHO_BIN: &str = "sway/echo/out/release/echo.bin";
+
+/// A scenario deploying the echo contract before running `steps`, written to `dir`.
+fn write_scenario(
+    dir: &Path,
+    name: &str,
+    wallets: &str,
+    steps: &str,
+) -> color_eyre::Result<Scenario> {
+    let path = dir.join(format!("{name}.toml"));
+    let deploy = format!("[[steps]]\nstep = \"deploy\"\nbin = \"{ECHO_BIN}\"\n");
+    std::fs::write(&path, format!("{wallets}\n{deploy}\n{steps}"))?;
+
+    Scenario::load(path)
+}
+
+#[tokio::test]
+async fn scenario_steps_are_checked() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    // of its own since the tests run in parallel
+    let dir = std::env::temp_dir().join(format!("e2e-scenarios-{}-steps", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+
+    let scenario = write_scenario(
+        &dir,
+        "echo",
+        "wallets = { alice = 10_000 }",
+        r#"
+        [[steps]]
+        step = "call"
+        contract = "echo"
+        method = "echo"
+        args = [42]
+        returns = "0x2a"
+        logs = [42]
+        cost = "echo"
+
+        [[steps]]
+        step = "call"
+        contract = "echo"
+        method = "echo_point"
+        args = [{ x = 7 }]
+        returns = { x = 7 }
+
+        [[steps]]
+        step = "call"
+        contract = "echo"
+        method = "echo"
+        args = ["18446744073709551615"]
+        amount = 100
+        returns = "18446744073709551615"
+
+        [[steps]]
+        step = "call"
+        contract = "echo"
+        method = "echo_nonzero"
+        args = [0]
+        reverts = "Zero"
+
+        [[steps]]
+        step = "transfer"
+        to = "alice"
+        amount = 500
+
+        [[steps]]
+        step = "balance"
+        of = "alice"
+        equals = 10_500
+        "#,
+    )?;
+    assert_eq!(scenario.name, "echo");
+    assert_eq!(scenario.steps.len(), 7);
+
+    let report = scenario.run(&setup).await?;
+    let steps = report
+        .transactions
+        .iter()
+        .map(|(step, _)| step.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        steps,
+        [
+            "call echo.echo",
+            "call echo.echo_point",
+            "call echo.echo",
+            "transfer 500 to alice"
+        ]
+    );
+    assert!(report
+        .transactions
+        .iter()
+        .all(|(_, outcome)| outcome.is_success()));
+
+    for (name, steps, error) in [
+        (
+            "wrong_return",
+            "method = \"echo\"\nargs = [42]\nreturns = 41",
+            "expected return value 41, got 42",
+        ),
+        (
+            "wrong_logs",
+            "method = \"echo\"\nargs = [42]\nlogs = [42, 42]",
+            "expected logs [42,42], got [42]",
+        ),
+        (
+            "wrong_argument",
+            "method = \"echo\"\nargs = [\"forty-two\"]",
+            "invalid argument value",
+        ),
+        (
+            "unknown_field",
+            "method = \"echo_point\"\nargs = [{ y = 7 }]",
+            "struct Point has no field y",
+        ),
+        (
+            "unknown_method",
+            "method = \"echoo\"",
+            "only echo, echo_nonzero, echo_point",
+        ),
+        (
+            "not_payable",
+            "method = \"echo_point\"\nargs = [{ x = 7 }]\namount = 1",
+            "non-payable",
+        ),
+        (
+            "no_revert",
+            "method = \"echo\"\nargs = [42]\nreverts = \"\"",
+            "expected the call to revert",
+        ),
+        (
+            "wrong_revert",
+            "method = \"echo_nonzero\"\nargs = [0]\nreverts = \"One\"",
+            "expected the call to revert with 'One', it reverted with 'Zero'",
+        ),
+        (
+            // more than the wallet holds, never submitted. Any revert expected, not any error
+            "rejected_without_reverting",
+            "method = \"echo\"\nargs = [1]\namount = 9_000_000_000_000_000_000\nreverts = \"\"",
+            "the call failed without reverting",
+        ),
+    ] {
+        let scenario = write_scenario(
+            &dir,
+            name,
+            "",
+            &format!("[[steps]]\nstep = \"call\"\ncontract = \"echo\"\n{steps}"),
+        )?;
+        let err = scenario.run(&setup).await.expect_err(name);
+        let err = format!("{err:#}");
+        assert!(err.contains("step 2 of scenario"), "{err}");
+        assert!(err.contains(error), "{name}: {err}");
+    }
+
+    // the wallets are swept when a step fails too
+    let scenario = write_scenario(
+        &dir,
+        "failing_with_wallets",
+        "wallets = { bob = 10_000 }",
+        "[[steps]]\nstep = \"call\"\ncontract = \"echo\"\nmethod = \"echoo\"\nfrom = \"bob\"",
+    )?;
+    let err = scenario.run(&setup).await.expect_err("unknown method");
+    assert!(format!("{err:#}").contains("only echo"), "{err:#}");
+    let bob = setup
+        .fresh_wallet_named("scenario/failing_with_wallets/bob", 0)
+        .await?;
+    let base_asset_id = *setup
+        .wallet
+        .try_provider()?
+        .consensus_parameters()
+        .await?
+        .base_asset_id();
+    assert_eq!(bob.get_asset_balance(&base_asset_id).await?, 0);
+    bob.sweep().await?;
+
+    // TOML integers are signed, larger ones are given as strings
+    let err = write_scenario(
+        &dir,
+        "too_large",
+        "",
+        "[[steps]]\nstep = \"call\"\ncontract = \"echo\"\nmethod = \"echo\"\n\
+         returns = 18446744073709551615",
+    )
+    .expect_err("u64::MAX as a TOML integer");
+    assert!(format!("{err:#}").contains("invalid scenario"), "{err:#}");
+
+    std::fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
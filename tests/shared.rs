@@ -0,0 +1,42 @@
+fuel_e2e_tests::define_fuels!();
+
+use fuel_e2e_tests::setup;
+use fuels::accounts::ViewOnlyAccount;
+
+// Each test runs on a runtime of its own, the shared setup must outlive all but one of them.
+async fn uses_the_shared_setup() -> color_eyre::Result<()> {
+    let first = setup::shared().await?;
+    let second = setup::shared().await?;
+
+    assert_eq!(
+        first.wallet.try_provider()?.url(),
+        second.wallet.try_provider()?.url()
+    );
+
+    let base_asset_id = *second
+        .wallet
+        .try_provider()?
+        .consensus_parameters()
+        .await?
+        .base_asset_id();
+    let wallet = first.fresh_wallet(1_000).await?;
+    assert_eq!(wallet.get_asset_balance(&base_asset_id).await?, 1_000);
+    wallet.sweep().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn shared_setup_1() -> color_eyre::Result<()> {
+    uses_the_shared_setup().await
+}
+
+#[tokio::test]
+async fn shared_setup_2() -> color_eyre::Result<()> {
+    uses_the_shared_setup().await
+}
+
+#[tokio::test]
+async fn shared_setup_3() -> color_eyre::Result<()> {
+    uses_the_shared_setup().await
+}
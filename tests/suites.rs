@@ -0,0 +1,160 @@
+fuel_e2e_tests::define_fuels!();
+
+use std::process::Command;
+
+use fuel_e2e_tests::{
+    report::{RunReport, TestReport},
+    setup, suites,
+};
+
+#[tokio::test]
+async fn transfers() -> color_eyre::Result<()> {
+    suites::run_test("transfers").await
+}
+
+#[tokio::test]
+async fn predicates() -> color_eyre::Result<()> {
+    suites::run_test("predicates").await
+}
+
+/// Transfers to its wallet and then fails on its balance.
+const FAILING_SCENARIO: &str = r#"
+wallets = { alice = 1_000 }
+
+[[steps]]
+step = "transfer"
+to = "alice"
+amount = 100
+
+[[steps]]
+step = "balance"
+of = "alice"
+equals = 1
+"#;
+
+#[tokio::test]
+async fn failed_suites_keep_their_transactions() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let dir = std::env::temp_dir().join(format!("e2e-suites-{}-failing", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("failing.toml"), FAILING_SCENARIO)?;
+
+    let suite = suites::all(&dir)?
+        .into_iter()
+        .find(|suite| suite.name == "scenario:failing")
+        .expect("loaded");
+    let run = suite.run(&setup).await;
+    std::fs::remove_dir_all(&dir)?;
+
+    assert!(!run.passed());
+    let report = TestReport::from(&run);
+    assert_eq!(
+        report.failure,
+        [
+            "step 2 of scenario 'failing' failed: balance of alice",
+            "expected balance 1, got 1100"
+        ]
+    );
+    assert_eq!(report.transactions.len(), 1);
+    assert_eq!(report.transactions[0].name, "transfer 100 to alice");
+    assert!(report.transactions[0].success);
+
+    Ok(())
+}
+
+fn fuel_e2e(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_fuel-e2e"))
+        .args(args)
+        .env_remove("E2E_PROFILE")
+        .output()
+        .expect("fuel-e2e runs")
+}
+
+#[test]
+fn binary_lists_and_runs_suites() {
+    let output = fuel_e2e(&["list"]);
+    assert!(output.status.success());
+    let listed = String::from_utf8_lossy(&output.stdout);
+    for suite in [
+        "transfers",
+        "liquidity_pool",
+        "multi_call",
+        "scenario:counter",
+    ] {
+        assert!(listed.contains(suite), "{listed}");
+    }
+
+    let output = fuel_e2e(&["run", "no-such-suite", "--chain", "local"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown suite no-such-suite"));
+
+    let output = fuel_e2e(&["run", "transfers", "--chain", "moon"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid target chain value: moon"));
+
+    let output = fuel_e2e(&["run", "transfers", "predicates", "--chain", "local"]);
+    let summary = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{summary}");
+    assert!(
+        summary.contains("2 suites: 2 passed, 0 failed"),
+        "{summary}"
+    );
+
+    // a failing scenario, with the reports written
+    let dir = std::env::temp_dir().join(format!("e2e-suites-{}", std::process::id()));
+    let scenarios = dir.join("scenarios");
+    std::fs::create_dir_all(&scenarios).expect("temp dir");
+    std::fs::write(scenarios.join("failing.toml"), FAILING_SCENARIO).expect("scenario written");
+    let reports = dir.join("reports");
+    let output = fuel_e2e(&[
+        "run",
+        "transfers",
+        "scenario:failing",
+        "--chain",
+        "local",
+        "--scenarios",
+        scenarios.to_str().expect("utf-8 path"),
+        "--report-dir",
+        reports.to_str().expect("utf-8 path"),
+    ]);
+    let summary = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{summary}");
+    assert!(
+        summary.contains("2 suites: 1 passed, 1 failed"),
+        "{summary}"
+    );
+
+    let mut written = std::fs::read_dir(&reports)
+        .expect("reports written")
+        .map(|entry| entry.expect("readable").path())
+        .collect::<Vec<_>>();
+    written.sort();
+    assert_eq!(written.len(), 2, "{written:?}");
+
+    let report: RunReport =
+        serde_json::from_str(&std::fs::read_to_string(&written[0]).expect("json report"))
+            .expect("valid report");
+    assert_eq!(report.failures(), 1);
+    assert_eq!(report.tests[0].name, "transfers");
+    assert_eq!(report.tests[0].transactions.len(), 1);
+    assert!(report.tests[0].transactions[0].total_fee > 0);
+    assert_eq!(
+        report.tests[1].failure,
+        [
+            "step 2 of scenario 'failing' failed: balance of alice",
+            "expected balance 1, got 1100"
+        ]
+    );
+    // the transactions before the failing step are reported too
+    assert_eq!(report.tests[1].transactions.len(), 1);
+    assert_eq!(
+        report.tests[1].transactions[0].name,
+        "transfer 100 to alice"
+    );
+
+    let junit = std::fs::read_to_string(&written[1]).expect("junit report");
+    assert!(junit.contains("tests=\"2\" failures=\"1\""), "{junit}");
+    assert!(junit.contains("<failure message=\"step 2 of scenario &apos;failing&apos; failed"));
+
+    std::fs::remove_dir_all(&dir).expect("temp dir removed");
+}
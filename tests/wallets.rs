@@ -0,0 +1,69 @@
+fuel_e2e_tests::define_fuels!();
+
+use fuel_e2e_tests::{
+    setup::{self, Setup},
+    wallets,
+};
+use fuels::accounts::ViewOnlyAccount;
+
+#[tokio::test]
+async fn fresh_wallet_is_funded_and_swept_back() -> color_eyre::Result<()> {
+    let setup = setup::init().await?;
+    let Setup { wallet, .. } = &setup;
+    let base_asset_id = *wallet
+        .try_provider()?
+        .consensus_parameters()
+        .await?
+        .base_asset_id();
+
+    let fresh = setup.fresh_wallet(10_000).await?;
+    assert_eq!(fresh.name(), "fresh_wallet_is_funded_and_swept_back");
+
+    let other = setup.fresh_wallet_named("some other test", 0).await?;
+    assert_ne!(fresh.address(), other.address());
+    other.sweep().await?;
+
+    let fresh_balance = fresh.get_asset_balance(&base_asset_id).await?;
+    assert!(fresh_balance >= 10_000);
+
+    let address = fresh.address().clone();
+    let pre_sweep_balance = wallet.get_asset_balance(&base_asset_id).await?;
+    let outcome = fresh.sweep().await?.expect("had funds to sweep");
+    assert!(outcome.is_success());
+    assert!(outcome.total_gas > 0);
+    let post_sweep_balance = wallet.get_asset_balance(&base_asset_id).await?;
+
+    assert_eq!(
+        post_sweep_balance,
+        pre_sweep_balance + fresh_balance - outcome.total_fee
+    );
+    assert_eq!(
+        wallet
+            .try_provider()?
+            .get_asset_balance(&address, base_asset_id)
+            .await?,
+        0
+    );
+
+    // derived from the test name, so the same wallet is handed out again
+    let again = setup.fresh_wallet(0).await?;
+    assert_eq!(again.address(), &address);
+    again.sweep().await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn spawned_tasks_have_no_test_name() -> color_eyre::Result<()> {
+    assert_eq!(
+        wallets::current_test_name()?,
+        "spawned_tasks_have_no_test_name"
+    );
+
+    let err = tokio::spawn(async { wallets::current_test_name() })
+        .await?
+        .expect_err("runs on a worker of the runtime");
+    assert!(format!("{err}").contains("not running on a test thread"));
+
+    Ok(())
+}